@@ -0,0 +1,178 @@
+use std::{cmp::Ordering, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// A puzzle answer, AoC answers are usually numbers but can be negative, huge, or text.
+///
+/// Build these with `From`/`Into` so numbers always end up in the same variant:
+/// non-negative integers are [`AnswerValue::Unsigned`], negative ones are [`AnswerValue::Signed`]
+/// and strings that look like integers are parsed as numbers.
+#[derive(Debug, Clone, Eq)]
+pub enum AnswerValue {
+    Signed(i128),
+    Unsigned(u128),
+    Text(String),
+}
+
+impl AnswerValue {
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, AnswerValue::Text(_))
+    }
+
+    /// Compares two numeric answers, text answers can only be equal or not
+    pub fn cmp_numeric(&self, other: &AnswerValue) -> Option<Ordering> {
+        match (self, other) {
+            (AnswerValue::Signed(a), AnswerValue::Signed(b)) => Some(a.cmp(b)),
+            (AnswerValue::Unsigned(a), AnswerValue::Unsigned(b)) => Some(a.cmp(b)),
+            (AnswerValue::Signed(a), AnswerValue::Unsigned(b)) => Some(compare_mixed(*a, *b)),
+            (AnswerValue::Unsigned(a), AnswerValue::Signed(b)) => {
+                Some(compare_mixed(*b, *a).reverse())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn compare_mixed(signed: i128, unsigned: u128) -> Ordering {
+    match u128::try_from(signed) {
+        Ok(signed) => signed.cmp(&unsigned),
+        Err(_) => Ordering::Less,
+    }
+}
+
+impl PartialEq for AnswerValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AnswerValue::Text(a), AnswerValue::Text(b)) => a == b,
+            _ => self.cmp_numeric(other) == Some(Ordering::Equal),
+        }
+    }
+}
+
+impl fmt::Display for AnswerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerValue::Signed(value) => write!(f, "{}", value),
+            AnswerValue::Unsigned(value) => write!(f, "{}", value),
+            AnswerValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<&str> for AnswerValue {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+        if let Ok(value) = value.parse::<u128>() {
+            AnswerValue::Unsigned(value)
+        } else if let Ok(value) = value.parse::<i128>() {
+            AnswerValue::Signed(value)
+        } else {
+            AnswerValue::Text(value.to_string())
+        }
+    }
+}
+
+impl From<String> for AnswerValue {
+    fn from(value: String) -> Self {
+        AnswerValue::from(value.as_str())
+    }
+}
+
+impl From<&String> for AnswerValue {
+    fn from(value: &String) -> Self {
+        AnswerValue::from(value.as_str())
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for AnswerValue {
+            fn from(value: $ty) -> Self {
+                AnswerValue::Unsigned(value as u128)
+            }
+        }
+    )*};
+}
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for AnswerValue {
+            fn from(value: $ty) -> Self {
+                if value < 0 {
+                    AnswerValue::Signed(value as i128)
+                } else {
+                    AnswerValue::Unsigned(value as u128)
+                }
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Always saved as a string so 128 bit values survive every serde format
+impl Serialize for AnswerValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts strings as well as bare integers so hand written answer files can use either
+impl<'de> Deserialize<'de> for AnswerValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+        impl serde::de::Visitor<'_> for AnswerVisitor {
+            type Value = AnswerValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer or a string")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+        }
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[test]
+fn answer_values_compare() {
+    use crate::Results;
+    assert_eq!(AnswerValue::from(42u64), AnswerValue::from("42"));
+    assert_eq!(AnswerValue::from(7i32), AnswerValue::Unsigned(7));
+    assert_eq!(AnswerValue::from("-3"), AnswerValue::Signed(-3));
+    assert_eq!(
+        AnswerValue::from(" 1,2,3 "),
+        AnswerValue::Text("1,2,3".into())
+    );
+    assert_eq!(
+        Results::compare(&AnswerValue::from(10u64), &AnswerValue::from(-5i64)),
+        Results::Higher
+    );
+    assert_eq!(
+        Results::compare(&AnswerValue::from(u128::MAX), &AnswerValue::from(u128::MAX)),
+        Results::Correct
+    );
+    assert_eq!(
+        Results::compare(&AnswerValue::from("abc"), &AnswerValue::from(1u8)),
+        Results::Incorrect
+    );
+}
@@ -0,0 +1,34 @@
+//! Turns a plaintext answer key into one that is safe to share
+//!
+//! `cargo run --bin hash_answers -- [input] [output] [--window <percent> | --no-bounds]`
+use advent_of_bevy_2025::{AnswerKey, HashedAnswerKey};
+
+fn main() -> Result<(), String> {
+    let mut paths = Vec::new();
+    let mut window = Some(10);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-bounds" => window = None,
+            "--window" => {
+                let percent = args.next().ok_or("--window needs a percentage")?;
+                window = Some(
+                    percent
+                        .parse()
+                        .map_err(|_| format!("{} is not a percentage", percent))?,
+                );
+            }
+            _ => paths.push(arg),
+        }
+    }
+    let input = paths.first().map_or(AnswerKey::PATH, String::as_str);
+    let output = paths.get(1).map_or(HashedAnswerKey::PATH, String::as_str);
+
+    let key = AnswerKey::load(input)?;
+    let hashed = HashedAnswerKey::from_key(&key, window)
+        .to_ron()
+        .map_err(|e| e.to_string())?;
+    std::fs::write(output, hashed).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    println!("Wrote hashed answers from {} to {}", input, output);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use advent_of_bevy_2025::{HeadlessPlugin, ImportMode, OutputFormat, StoreBackend, Transfer};
+use bevy::prelude::*;
+
+const USAGE: &str =
+    "usage: headless [--json] [--input-dir <path>] [--saves <file.json> | --no-saves]
+                [--export <path> | --import <path> [--merge | --overwrite]]";
+
+fn main() -> AppExit {
+    let mut format = OutputFormat::Table;
+    let mut input_dir = None;
+    let mut transfer = None;
+    let mut mode = None;
+    let mut store = StoreBackend::Pkv;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        // a typo running with the defaults could merge a save that was meant to be overwritten
+        let Some(flag) = Flag::parse(&arg) else {
+            return usage(&format!("unknown argument {}", arg));
+        };
+        let value = if flag.takes_value() {
+            match args.next() {
+                Some(value) => value,
+                None => return usage(&format!("{} needs a value", arg)),
+            }
+        } else {
+            String::new()
+        };
+        match flag {
+            Flag::Json => format = OutputFormat::Json,
+            Flag::InputDir => input_dir = Some(value.into()),
+            Flag::Export => transfer = Some(Transfer::Export(value.into())),
+            Flag::Import => {
+                transfer = Some(Transfer::Import {
+                    path: value.into(),
+                    mode: None,
+                })
+            }
+            Flag::Merge => mode = Some(ImportMode::Merge),
+            Flag::Overwrite => mode = Some(ImportMode::Overwrite),
+            Flag::Saves => store = StoreBackend::JsonFile(value.into()),
+            Flag::NoSaves => store = StoreBackend::Memory,
+        }
+    }
+
+    if let Some(Transfer::Import { mode: import, .. }) = &mut transfer {
+        *import = mode;
+    }
+
+    let mut app = App::new();
+
+    app.add_plugins(HeadlessPlugin {
+        format,
+        input_dir,
+        transfer,
+        store,
+    });
+
+    app.run()
+}
+
+enum Flag {
+    Json,
+    InputDir,
+    Export,
+    Import,
+    Merge,
+    Overwrite,
+    Saves,
+    NoSaves,
+}
+
+impl Flag {
+    fn parse(arg: &str) -> Option<Flag> {
+        Some(match arg {
+            "--json" => Flag::Json,
+            "--input-dir" => Flag::InputDir,
+            "--export" => Flag::Export,
+            "--import" => Flag::Import,
+            "--merge" => Flag::Merge,
+            "--overwrite" => Flag::Overwrite,
+            "--saves" => Flag::Saves,
+            "--no-saves" => Flag::NoSaves,
+            _ => return None,
+        })
+    }
+
+    fn takes_value(&self) -> bool {
+        matches!(
+            self,
+            Flag::InputDir | Flag::Export | Flag::Import | Flag::Saves
+        )
+    }
+}
+
+fn usage(problem: &str) -> AppExit {
+    eprintln!("{}\n{}", problem, USAGE);
+    AppExit::error()
+}
@@ -1,6 +1,19 @@
+use std::{any::Any, collections::BTreeMap};
+
 use bevy::{platform::collections::HashMap, prelude::*};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::{Day, PKVKeys, calendar::CalendarState, state::Puzzle};
+use crate::{
+    AnswerValue, Day, EVENT_YEAR, KeyEntry, PKVKeys, SaveStore, Toast, Year,
+    calendar::CalendarState,
+    cooldown::{CooldownPolicy, Cooldowns, format_remaining},
+    input::{CurrentDayInput, InputDir, InputSelection},
+    providers::{AnswerKey, AnswerProvider, HashedAnswerKey, ManualAnswers, ReferenceSolvers},
+    save::{Versioned, load_or_report},
+    state::Puzzle,
+    timing::SolveClock,
+};
 
 #[derive(Resource)]
 pub struct CurrentDayRaw(pub String);
@@ -11,36 +24,76 @@ impl FromWorld for CurrentDayRaw {
     }
 }
 
-impl CurrentDayRaw {
-    pub fn load_day(day: u8) -> Option<Self> {
-        let path = format!("assets/days/day{:02}.input", day);
-        std::fs::read_to_string(path).ok().map(CurrentDayRaw)
-    }
-}
-
-pub fn update_day(set: On<Day>, mut raw: ResMut<CurrentDayRaw>, mut next: ResMut<NextState<Day>>) {
-    next.set(*set);
+/// Starts loading the day's input, [`PuzzleInputPlugin`](crate::PuzzleInputPlugin) opens the day once it has loaded
+pub fn update_day(
+    set: On<Day>,
+    mut next: ResMut<NextState<Day>>,
+    (selection, dir): (Res<InputSelection>, Res<InputDir>),
+    mut current: ResMut<CurrentDayInput>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
     if set.0 == 0 {
+        next.set(*set);
         return;
     }
-    if let Some(data) = CurrentDayRaw::load_day(set.0) {
-        *raw = data;
-    } else {
-        next.set(Day(0));
-        error!("Failed to load data for day {}", set.0);
+    current.load(
+        *set,
+        selection.get(set.0),
+        &dir,
+        &asset_server,
+        &mut commands,
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingReason {
+    Missing,
+    Empty,
+}
+
+impl std::fmt::Display for MissingReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissingReason::Missing => write!(f, "missing"),
+            MissingReason::Empty => write!(f, "empty"),
+        }
     }
 }
 
-#[derive(Resource)]
+/// Triggered instead of opening a day whose input file is missing or empty
+#[derive(Event, Debug, Clone)]
+pub struct MissingInput {
+    pub day: Day,
+    pub name: String,
+    pub path: std::path::PathBuf,
+    pub reason: MissingReason,
+}
+
+#[derive(Resource, Serialize, Deserialize)]
 pub struct Seed(pub u64);
+
+impl Versioned for Seed {
+    const VERSION: u32 = 0;
+}
+
+impl Seed {
+    /// Picks a new seed and saves it
+    pub fn reroll(&mut self, pkv: &mut SaveStore) {
+        self.0 = rand::random();
+        if let Err(e) = pkv.set(PKVKeys::Seed, &self.0) {
+            error!("Failed to save seed: {}", e);
+        }
+    }
+}
 impl FromWorld for Seed {
     fn from_world(world: &mut World) -> Self {
-        if let Ok(seed) = world.resource::<bevy_pkv::PkvStore>().get(PKVKeys::Seed) {
-            Seed(seed)
+        if let Some(seed) = load_or_report(world, PKVKeys::Seed.as_ref().to_string()) {
+            seed
         } else {
             let seed = rand::random();
             world
-                .resource_mut::<bevy_pkv::PkvStore>()
+                .resource_mut::<SaveStore>()
                 .set(PKVKeys::Seed, &seed)
                 .unwrap();
             Seed(seed)
@@ -48,90 +101,518 @@ impl FromWorld for Seed {
     }
 }
 
-#[derive(Resource, Default)]
-pub(crate) struct Anwsers {
-    anwsers: HashMap<(usize, Puzzle), u64>,
+/// The [`AnswerProvider`]s answers are checked against, in the order they are asked
+#[derive(Resource)]
+pub struct Anwsers {
+    providers: Vec<Box<dyn AnswerProvider>>,
+    /// Reference answers only count when they were computed from the real input
+    pub(crate) real_input: bool,
+    practice: HashMap<(u8, Puzzle), AnswerValue>,
+}
+
+impl FromWorld for Anwsers {
+    fn from_world(world: &mut World) -> Self {
+        let mut answers = Anwsers {
+            providers: Vec::new(),
+            real_input: true,
+            practice: HashMap::new(),
+        };
+        answers.register(ReferenceSolvers::default());
+        let year = *world.resource::<Year>();
+        // the answer key files are for the current event
+        if year.year == EVENT_YEAR && std::path::Path::new(AnswerKey::PATH).exists() {
+            match AnswerKey::load(AnswerKey::PATH) {
+                Ok(key) => answers.register(key),
+                Err(e) => error!("{}", e),
+            }
+        }
+        if year.year == EVENT_YEAR && std::path::Path::new(HashedAnswerKey::PATH).exists() {
+            match HashedAnswerKey::load(HashedAnswerKey::PATH) {
+                Ok(key) => answers.register(key),
+                Err(e) => error!("{}", e),
+            }
+        }
+        let manual: ManualAnswers =
+            load_or_report(world, year.key(PKVKeys::ManualAnswers)).unwrap_or_default();
+        answers.register(manual);
+        answers
+    }
 }
 
 impl Anwsers {
-    pub fn add(&mut self, day: usize, puzzle: Puzzle, answer: u64) {
-        self.anwsers.insert((day, puzzle), answer);
-    }
-    pub fn check(&self, day: usize, puzzle: Puzzle, answer: u64) -> Results {
-        if let Some(correct) = self.anwsers.get(&(day, puzzle)) {
-            if *correct == answer {
-                Results::Correct
-            } else if *correct < answer {
-                Results::Higher
-            } else {
-                Results::Lower
+    /// Adds a provider after the ones already registered
+    pub fn register(&mut self, provider: impl AnswerProvider) {
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Keeps the providers that were registered on `old` on top of the built in ones
+    pub(crate) fn keep_registered(&mut self, old: Anwsers) {
+        let builtin = |provider: &dyn Any| {
+            provider.is::<ReferenceSolvers>()
+                || provider.is::<AnswerKey>()
+                || provider.is::<HashedAnswerKey>()
+                || provider.is::<ManualAnswers>()
+        };
+        self.providers.extend(
+            old.providers
+                .into_iter()
+                .filter(|provider| !builtin(provider.as_ref() as &dyn Any)),
+        );
+    }
+
+    pub fn provider<T: AnswerProvider>(&self) -> Option<&T> {
+        self.providers
+            .iter()
+            .find_map(|provider| (provider.as_ref() as &dyn Any).downcast_ref())
+    }
+
+    pub fn provider_mut<T: AnswerProvider>(&mut self) -> Option<&mut T> {
+        self.providers
+            .iter_mut()
+            .find_map(|provider| (provider.as_mut() as &mut dyn Any).downcast_mut())
+    }
+
+    /// Stores the reference solver's answer, answers from an input other than the real one
+    /// are kept apart so they never confirm a star
+    pub fn add(&mut self, day: usize, puzzle: Puzzle, answer: impl Into<AnswerValue>) {
+        if !self.real_input {
+            self.practice.insert((day as u8, puzzle), answer.into());
+        } else if let Some(reference) = self.provider_mut::<ReferenceSolvers>() {
+            reference.add(day as u8, puzzle, answer.into());
+        }
+    }
+
+    /// The reference solver's answer for the selected input that isn't the real one
+    pub fn practice(&self, day: u8, puzzle: Puzzle) -> Option<&AnswerValue> {
+        self.practice.get(&(day, puzzle))
+    }
+
+    /// The reference solver's answer
+    pub fn get(&self, day: u8, puzzle: Puzzle) -> Option<&AnswerValue> {
+        self.provider::<ReferenceSolvers>()
+            .and_then(|reference| reference.get(day, puzzle))
+    }
+
+    /// Asks each provider in order, returning the first result that isn't [`Results::Missing`]
+    /// along with the name of the provider that gave it
+    pub fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> (Results, Option<&str>) {
+        for provider in &self.providers {
+            let result = provider.check(day, puzzle, answer);
+            if result != Results::Missing {
+                return (result, Some(provider.name()));
             }
-        } else {
-            Results::Missing
         }
+        (Results::Missing, None)
     }
 }
 
-enum Results {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Results {
     Missing,
     Correct,
+    /// Wrong, and the correct answer is lower
     Lower,
+    /// Wrong, and the correct answer is higher
     Higher,
+    /// Wrong, with no hint because the answer is not a number
+    Incorrect,
 }
 
+impl Results {
+    pub fn compare(correct: &AnswerValue, answer: &AnswerValue) -> Results {
+        if correct == answer {
+            return Results::Correct;
+        }
+        match correct.cmp_numeric(answer) {
+            Some(std::cmp::Ordering::Less) => Results::Lower,
+            Some(_) => Results::Higher,
+            None => Results::Incorrect,
+        }
+    }
+}
+
+/// `Part1`/`Part2` are checked against the day that is currently open,
+/// use [`Submit::for_day`] to pick the day yourself
 #[derive(Event)]
 pub enum Submit {
     Part1(u64),
     Part2(u64),
+    /// Any [`AnswerValue`] for the open day, see [`Submit::part1`] and [`Submit::part2`]
+    Answer {
+        puzzle: Puzzle,
+        answer: AnswerValue,
+    },
+    ForDay {
+        day: Day,
+        puzzle: Puzzle,
+        answer: AnswerValue,
+    },
+}
+
+impl Submit {
+    pub fn part1(answer: impl Into<AnswerValue>) -> Submit {
+        Submit::Answer {
+            puzzle: Puzzle::Part1,
+            answer: answer.into(),
+        }
+    }
+
+    pub fn part2(answer: impl Into<AnswerValue>) -> Submit {
+        Submit::Answer {
+            puzzle: Puzzle::Part2,
+            answer: answer.into(),
+        }
+    }
+
+    pub fn for_day(day: Day, puzzle: Puzzle, answer: impl Into<AnswerValue>) -> Submit {
+        Submit::ForDay {
+            day,
+            puzzle,
+            answer: answer.into(),
+        }
+    }
+
+    /// The day this should be checked against, or why there isn't one
+    fn target(
+        &self,
+        current: Day,
+        next: &NextState<Day>,
+        year: &Year,
+    ) -> Result<(Day, Puzzle, AnswerValue), String> {
+        let (day, puzzle, answer) = match self {
+            Submit::Part1(answer) => (None, Puzzle::Part1, AnswerValue::from(*answer)),
+            Submit::Part2(answer) => (None, Puzzle::Part2, AnswerValue::from(*answer)),
+            Submit::Answer { puzzle, answer } => (None, *puzzle, answer.clone()),
+            Submit::ForDay {
+                day,
+                puzzle,
+                answer,
+            } => (Some(*day), *puzzle, answer.clone()),
+        };
+        let day = match day {
+            Some(day) => day,
+            None => {
+                if let NextState::Pending(next) = next
+                    && *next != current
+                {
+                    return Err(format!(
+                        "Can't submit while changing from day {} to day {}; use Submit::for_day",
+                        current.0, next.0
+                    ));
+                }
+                current
+            }
+        };
+        if day.0 == 0 {
+            return Err("No day is open to submit to; use Submit::for_day".to_string());
+        }
+        if !year.contains(day.0) {
+            return Err(format!(
+                "Day {} is not part of the {} calendar",
+                day.0, year.year
+            ));
+        }
+        Ok((day, puzzle, answer))
+    }
+}
+
+/// A single guess sent through [`Submit`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub timestamp: DateTime<Utc>,
+    pub value: AnswerValue,
+    pub result: Results,
+    /// Name of the [`AnswerProvider`] that checked this attempt
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DayHistory {
+    pub part1: Vec<Attempt>,
+    pub part2: Vec<Attempt>,
+}
+
+impl DayHistory {
+    pub fn attempts(&self, puzzle: Puzzle) -> &[Attempt] {
+        match puzzle {
+            Puzzle::Part1 => &self.part1,
+            Puzzle::Part2 => &self.part2,
+        }
+    }
+}
+
+/// Every answer submitted in the open [`Year`], kept across restarts under [`PKVKeys::History`]
+#[derive(Resource, Debug, Serialize, Deserialize)]
+pub struct SubmissionHistory {
+    days: BTreeMap<u8, DayHistory>,
+}
+
+impl Versioned for SubmissionHistory {
+    const VERSION: u32 = 1;
+}
+
+impl FromWorld for SubmissionHistory {
+    fn from_world(world: &mut World) -> Self {
+        let key = world.resource::<Year>().key(PKVKeys::History);
+        load_or_report(world, key).unwrap_or_else(|| SubmissionHistory {
+            days: BTreeMap::new(),
+        })
+    }
+}
+
+impl SubmissionHistory {
+    pub fn record(
+        &mut self,
+        day: u8,
+        puzzle: Puzzle,
+        value: AnswerValue,
+        result: Results,
+        source: Option<String>,
+    ) {
+        let day = self.days.entry(day).or_default();
+        let attempts = match puzzle {
+            Puzzle::Part1 => &mut day.part1,
+            Puzzle::Part2 => &mut day.part2,
+        };
+        attempts.push(Attempt {
+            timestamp: Utc::now(),
+            value,
+            result,
+            source,
+        });
+    }
+
+    /// Adds the attempts from `other` that aren't already here
+    pub fn merge(&mut self, other: SubmissionHistory) {
+        for (day, theirs) in other.days {
+            let ours = self.days.entry(day).or_default();
+            for (ours, theirs) in [
+                (&mut ours.part1, theirs.part1),
+                (&mut ours.part2, theirs.part2),
+            ] {
+                for attempt in theirs {
+                    let seen = ours.iter().any(|known| {
+                        known.timestamp == attempt.timestamp && known.value == attempt.value
+                    });
+                    if !seen {
+                        ours.push(attempt);
+                    }
+                }
+                ours.sort_by_key(|attempt| attempt.timestamp);
+            }
+        }
+    }
+
+    /// Forgets every attempt at a puzzle
+    pub fn clear(&mut self, day: u8, puzzle: Puzzle) {
+        if let Some(history) = self.days.get_mut(&day) {
+            match puzzle {
+                Puzzle::Part1 => history.part1.clear(),
+                Puzzle::Part2 => history.part2.clear(),
+            }
+        }
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayHistory> {
+        self.days.get(&day)
+    }
+
+    pub fn attempts(&self, day: u8, puzzle: Puzzle) -> &[Attempt] {
+        self.days
+            .get(&day)
+            .map(|history| history.attempts(puzzle))
+            .unwrap_or_default()
+    }
+
+    /// How many guesses it took to get the star, including the correct one
+    pub fn tries_to_solve(&self, day: u8, puzzle: Puzzle) -> Option<usize> {
+        self.attempts(day, puzzle)
+            .iter()
+            .position(|attempt| attempt.result == Results::Correct)
+            .map(|i| i + 1)
+    }
+
+    /// When the star was earned
+    pub fn first_solved(&self, day: u8, puzzle: Puzzle) -> Option<DateTime<Utc>> {
+        self.attempts(day, puzzle)
+            .iter()
+            .find(|attempt| attempt.result == Results::Correct)
+            .map(|attempt| attempt.timestamp)
+    }
+
+    /// The provider that confirmed the star, if it has been earned
+    pub fn solved_by(&self, day: u8, puzzle: Puzzle) -> Option<&str> {
+        self.attempts(day, puzzle)
+            .iter()
+            .find(|attempt| attempt.result == Results::Correct)
+            .and_then(|attempt| attempt.source.as_deref())
+    }
 }
 
 pub(super) fn submit_answers(
     submition: On<Submit>,
-    answers: Res<Anwsers>,
-    // mut pkv: ResMut<bevy_pkv::PkvStore>,
-    day: Res<State<Day>>,
+    (answers, policy, selection, dir): (
+        Res<Anwsers>,
+        Res<CooldownPolicy>,
+        Res<InputSelection>,
+        Res<InputDir>,
+    ),
+    (current, next, year): (Res<State<Day>>, Res<NextState<Day>>, Res<Year>),
     mut state: ResMut<CalendarState>,
-    mut pkv: ResMut<bevy_pkv::PkvStore>,
+    (mut history, mut cooldowns): (ResMut<SubmissionHistory>, ResMut<Cooldowns>),
+    (mut pkv, clock): (ResMut<SaveStore>, Option<ResMut<SolveClock>>),
+    mut commands: Commands,
 ) {
-    println!("Submitting answer for day {}", day.0);
-    match &*submition {
-        Submit::Part1(answer) => match answers.check(day.0 as usize, Puzzle::Part1, *answer) {
-            Results::Missing => error!(
-                "No answer stored for day {};\nPester Phox to add this day's answer!",
-                day.0
-            ),
-            Results::Correct => {
-                info!("Day {} Part 1 answer is correct!", day.0);
-                state.pass(day.0, Puzzle::Part1);
-                _ = pkv.set(PKVKeys::CalendarState25, &*state);
-            }
-            Results::Lower => info!(
-                "Day {} Part 1 answer is incorrect; the correct answer is lower.",
-                day.0
-            ),
-            Results::Higher => info!(
-                "Day {} Part 1 answer is incorrect; the correct answer is higher.",
-                day.0
-            ),
-        },
-        Submit::Part2(answer) => match answers.check(day.0 as usize, Puzzle::Part2, *answer) {
-            Results::Missing => error!(
+    let (day, puzzle, answer) = match submition.target(*current.get(), &next, &year) {
+        Ok(target) => target,
+        Err(message) => {
+            error!("{}", message);
+            commands.trigger(Toast::error(message));
+            return;
+        }
+    };
+    if !matches!(*submition, Submit::ForDay { .. })
+        && let Some(timing) = clock.and_then(|mut clock| clock.submitted(day.0, puzzle))
+    {
+        commands.trigger(timing);
+    }
+    if !matches!(*submition, Submit::ForDay { .. }) && !selection.is_real(day.0) {
+        let input = selection.get(day.0);
+        let expected = dir.expected(day.0, input);
+        check_practice(day.0, puzzle, &answer, input, expected, &mut commands);
+        return;
+    }
+    if policy.enabled
+        && let Some(remaining) = cooldowns.remaining(day.0, puzzle)
+    {
+        let message = format!(
+            "Day {} {} is locked for another {} after a wrong answer",
+            day.0,
+            puzzle,
+            format_remaining(remaining)
+        );
+        warn!("{}", message);
+        commands.trigger(Toast::error(message));
+        return;
+    }
+    debug!("Submitting day {} {}: {}", day.0, puzzle, answer);
+    let (result, source) = answers.check(day.0, puzzle, &answer);
+    history.record(
+        day.0,
+        puzzle,
+        answer.clone(),
+        result,
+        source.map(str::to_string),
+    );
+    _ = pkv.store(year.key(PKVKeys::History), &*history);
+    let mut message = match result {
+        Results::Missing => {
+            let message = format!(
                 "No answer stored for day {};\nPester Phox to add this day's answer!",
                 day.0
-            ),
-            Results::Correct => {
-                info!("Day {} Part 2 answer is correct!", day.0);
-                state.pass(day.0, Puzzle::Part2);
-                _ = pkv.set(PKVKeys::CalendarState25, &*state);
+            );
+            error!("{}", message);
+            message
+        }
+        Results::Correct => {
+            if let Err(e) = state.pass(day.0, puzzle) {
+                error!("{}", e);
+                commands.trigger(Toast::error(e));
+                return;
             }
-            Results::Lower => info!(
-                "Day {} Part 2 answer is incorrect; the correct answer is lower.",
-                day.0
-            ),
-            Results::Higher => info!(
-                "Day {} Part 2 answer is incorrect; the correct answer is higher.",
-                day.0
-            ),
-        },
+            _ = pkv.store(year.key(PKVKeys::CalendarState), &*state);
+            format!(
+                "Day {} {} answer is correct! (checked by {})",
+                day.0,
+                puzzle,
+                source.unwrap_or("unknown")
+            )
+        }
+        Results::Lower => format!(
+            "Day {} {} answer is incorrect; the correct answer is lower.",
+            day.0, puzzle
+        ),
+        Results::Higher => format!(
+            "Day {} {} answer is incorrect; the correct answer is higher.",
+            day.0, puzzle
+        ),
+        Results::Incorrect => format!("Day {} {} answer is incorrect.", day.0, puzzle),
+    };
+    if policy.enabled
+        && matches!(
+            result,
+            Results::Lower | Results::Higher | Results::Incorrect
+        )
+    {
+        let cooldown = cooldowns.wrong_answer(&policy, day.0, puzzle);
+        _ = pkv.store(year.key(PKVKeys::Cooldowns), &*cooldowns);
+        message.push_str(&format!(
+            "\nWait {} before trying again.",
+            format_remaining(cooldown)
+        ));
+    }
+    if result != Results::Missing {
+        info!("{}", message);
     }
+    commands.trigger(Toast::new(result.into(), message));
+}
+
+/// Checks an answer for an input other than the real one against the answers saved next to it,
+/// nothing is recorded
+fn check_practice(
+    day: u8,
+    puzzle: Puzzle,
+    answer: &AnswerValue,
+    input: &str,
+    expected: Option<KeyEntry>,
+    commands: &mut Commands,
+) {
+    let result = expected
+        .and_then(|expected| expected.get(puzzle).cloned())
+        .map_or(Results::Missing, |correct| {
+            Results::compare(&correct, answer)
+        });
+    let message = match result {
+        Results::Missing => format!(
+            "No expected answer for day {} {} with the {} input",
+            day, puzzle, input
+        ),
+        Results::Correct => format!(
+            "Day {} {} is correct for the {} input (not counted)",
+            day, puzzle, input
+        ),
+        Results::Lower | Results::Higher | Results::Incorrect => format!(
+            "Day {} {} is incorrect for the {} input (not counted)",
+            day, puzzle, input
+        ),
+    };
+    info!("{}", message);
+    commands.trigger(Toast::new(result.into(), message));
+}
+
+#[test]
+fn submit_targets() {
+    let none = NextState::<Day>::Unchanged;
+    let year = Year::aoc(2025);
+    let submit = Submit::for_day(Day(5), Puzzle::Part2, 7u8);
+    let (day, puzzle, _) = submit.target(Day(0), &none, &year).unwrap();
+    assert_eq!((day, puzzle), (Day(5), Puzzle::Part2));
+    assert!(Submit::part1(7u8).target(Day(0), &none, &year).is_err());
+    assert!(
+        Submit::part1(7u8)
+            .target(Day(3), &NextState::Pending(Day(0)), &year)
+            .is_err()
+    );
+    assert_eq!(
+        Submit::part1(7u8).target(Day(3), &none, &year).unwrap().0,
+        Day(3)
+    );
+    let (_, puzzle, answer) = Submit::Part2(42u64).target(Day(3), &none, &year).unwrap();
+    assert_eq!((puzzle, answer), (Puzzle::Part2, AnswerValue::from(42u64)));
+    assert!(
+        Submit::for_day(Day(13), Puzzle::Part1, 7u8)
+            .target(Day(3), &none, &year)
+            .is_err()
+    );
 }
@@ -1,15 +1,27 @@
 use bevy::feathers::controls::{ButtonProps, ButtonVariant, button};
 use bevy::feathers::cursor::EntityCursor;
+use bevy::feathers::theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText};
+use bevy::feathers::tokens;
+use bevy::input_focus::directional_navigation::{
+    DirectionalNavigation, DirectionalNavigationMap, DirectionalNavigationPlugin,
+};
 use bevy::input_focus::tab_navigation::{TabGroup, TabIndex};
+use bevy::input_focus::{InputFocus, InputFocusVisible};
+use bevy::math::CompassOctant;
 use bevy::picking::hover::Hovered;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
 use bevy::ui::InteractionDisabled;
-use bevy::{asset, prelude::*};
-use chrono::Datelike;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::save::{Versioned, load_or_report, report_save_problem};
 use crate::state::Puzzle;
-use crate::{Day, PKVKeys, Seed};
+use crate::{
+    Day, DayTitles, InputDir, PKVKeys, REAL_INPUT, ResetProgress, SaveStore, Seed, SelectYear,
+    SubmissionHistory, UnlockPolicy, Year, Years,
+};
 
 #[derive(Resource, Serialize, Deserialize)]
 pub(crate) struct CalendarState {
@@ -18,30 +30,113 @@ pub(crate) struct CalendarState {
 
 impl FromWorld for CalendarState {
     fn from_world(world: &mut World) -> Self {
-        let pkv = world.resource::<bevy_pkv::PkvStore>();
-        if let Ok(save) = pkv.get(PKVKeys::CalendarState25) {
-            save
-        } else {
-            CalendarState {
-                days: vec![
-                    DayState {
-                        puzzle1_completed: false,
-                        puzzle2_completed: false,
-                    };
-                    25
-                ],
-            }
+        let year = *world.resource::<Year>();
+        let mut state: CalendarState = load_or_report(world, year.key(PKVKeys::CalendarState))
+            .unwrap_or(CalendarState { days: Vec::new() });
+        let dropped = state.days.iter().enumerate().skip(year.days as usize);
+        let starred = dropped
+            .filter(|(_, day)| day.puzzle1_completed || day.puzzle2_completed)
+            .map(|(i, _)| (i + 1).to_string())
+            .collect::<Vec<_>>();
+        if !starred.is_empty() {
+            let message = format!(
+                "The {} calendar only has {} days, the stars saved for day {} were dropped",
+                year.year,
+                year.days,
+                starred.join(", ")
+            );
+            warn!("{}", message);
+            report_save_problem(world, message);
+        } else if state.days.len() > year.days as usize {
+            info!(
+                "The {} calendar only has {} days, dropped {} days without stars from its save",
+                year.year,
+                year.days,
+                state.days.len() - year.days as usize
+            );
+        }
+        state.days.resize(
+            year.days as usize,
+            DayState {
+                puzzle1_completed: false,
+                puzzle2_completed: false,
+            },
+        );
+        state
+    }
+}
+
+impl Versioned for CalendarState {
+    const VERSION: u32 = 1;
+
+    fn migrate(version: u32, save: Value) -> Result<Value, String> {
+        match version {
+            // the first calendars were saved as just the list of days
+            0 if save.is_array() => Ok(serde_json::json!({ "days": save })),
+            0 => Ok(save),
+            _ => Err(format!("no migration from version {}", version)),
         }
     }
 }
 
 impl CalendarState {
-    pub fn pass(&mut self, day: u8, puzzle: Puzzle) {
-        let day_state = &mut self.days[(day - 1) as usize];
+    /// Gives the star for `puzzle`, days that aren't on the calendar are an error
+    pub fn pass(&mut self, day: u8, puzzle: Puzzle) -> Result<(), String> {
+        let days = self.days.len();
+        let Some(day_state) = self.days.get_mut((day as usize).wrapping_sub(1)) else {
+            return Err(format!(
+                "Day {} is not on the calendar, it has days 1 to {}",
+                day, days
+            ));
+        };
         match puzzle {
             Puzzle::Part1 => day_state.puzzle1_completed = true,
             Puzzle::Part2 => day_state.puzzle2_completed = true,
         }
+        Ok(())
+    }
+
+    /// Takes a star away again
+    pub fn reset(&mut self, day: u8, puzzle: Puzzle) {
+        let Some(day_state) = self.days.get_mut((day as usize).wrapping_sub(1)) else {
+            return;
+        };
+        match puzzle {
+            Puzzle::Part1 => day_state.puzzle1_completed = false,
+            Puzzle::Part2 => day_state.puzzle2_completed = false,
+        }
+    }
+
+    /// Stars earned on a day, 0 to 2
+    pub fn stars(&self, day: u8) -> u8 {
+        self.days
+            .get((day as usize).wrapping_sub(1))
+            .map_or(0, |day| {
+                u8::from(day.puzzle1_completed) + u8::from(day.puzzle2_completed)
+            })
+    }
+
+    /// Keeps every star either calendar has
+    pub fn merge(&mut self, other: &CalendarState) {
+        if self.days.len() < other.days.len() {
+            self.days.resize(
+                other.days.len(),
+                DayState {
+                    puzzle1_completed: false,
+                    puzzle2_completed: false,
+                },
+            );
+        }
+        for (day, theirs) in self.days.iter_mut().zip(&other.days) {
+            day.puzzle1_completed |= theirs.puzzle1_completed;
+            day.puzzle2_completed |= theirs.puzzle2_completed;
+        }
+    }
+
+    pub fn first_star(&self, day: u8) -> bool {
+        self.days
+            .get((day as usize).wrapping_sub(1))
+            .is_some_and(|day| day.puzzle1_completed)
     }
 }
 
@@ -49,12 +144,322 @@ pub struct CalendarPlugin;
 
 impl Plugin for CalendarPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CalendarState>();
-        app.add_systems(OnEnter(Day(0)), spawn_calendar);
+        app.init_resource::<CalendarLayout>();
+        app.add_observer(set_layout);
+        app.add_observer(reroll_seed);
+        app.add_observer(|_: On<Year>, mut commands: Commands| {
+            commands.run_system_cached(respawn_calendar);
+        });
+        app.add_observer(|_: On<ResetProgress>, mut commands: Commands| {
+            commands.run_system_cached(respawn_calendar);
+        });
+        app.add_systems(
+            OnEnter(Day(0)),
+            (spawn_calendar, spawn_calendar_controls, spawn_year_picker),
+        );
         app.add_systems(OnExit(Day(0)), spawn_back_to_calendar);
+        app.add_plugins(DirectionalNavigationPlugin);
+        app.add_systems(
+            Update,
+            (navigate_calendar, show_day_details)
+                .chain()
+                .run_if(in_state(Day(0))),
+        );
+        app.add_systems(Update, back_to_calendar.run_if(not(in_state(Day(0)))));
+    }
+}
+
+/// How the days are laid out on the calendar, saved under [`PKVKeys::CalendarLayout`]
+#[derive(Resource, Event, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarLayout {
+    /// Shuffled by the [`Seed`], the default
+    Shuffled,
+    Ordered,
+    /// One column with the last day at the top, like the AoC site
+    List,
+    /// Round the edge and into the middle, so the last day is in the centre
+    Spiral,
+}
+
+impl FromWorld for CalendarLayout {
+    fn from_world(world: &mut World) -> Self {
+        load_or_report(world, PKVKeys::CalendarLayout.as_ref().to_string())
+            .unwrap_or(CalendarLayout::Shuffled)
+    }
+}
+
+impl Versioned for CalendarLayout {
+    const VERSION: u32 = 0;
+}
+
+/// Where each day goes on the calendar grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub rows: u16,
+    pub columns: u16,
+    /// `(day, row, column)`, rows and columns start at 0
+    pub tiles: Vec<(u8, u16, u16)>,
+}
+
+impl CalendarLayout {
+    pub const ALL: [CalendarLayout; 4] = [
+        CalendarLayout::Shuffled,
+        CalendarLayout::Ordered,
+        CalendarLayout::List,
+        CalendarLayout::Spiral,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalendarLayout::Shuffled => "Shuffled",
+            CalendarLayout::Ordered => "Ordered",
+            CalendarLayout::List => "List",
+            CalendarLayout::Spiral => "Spiral",
+        }
+    }
+
+    /// Places days `1..=days` on the smallest square-ish grid that fits them
+    pub fn place(&self, days: u8, seed: u64) -> Placement {
+        let count = days as u16;
+        let side = (1..).find(|side| side * side >= count).unwrap_or(1);
+        let columns = count.div_ceil(side).max(1);
+        let mut order = (1..=days).collect::<Vec<_>>();
+        match self {
+            CalendarLayout::Shuffled => {
+                order.shuffle(&mut rand::rngs::StdRng::seed_from_u64(seed));
+                // filled column by column
+                let tiles = order.into_iter().zip(0..);
+                Placement {
+                    rows: side,
+                    columns,
+                    tiles: tiles.map(|(day, i)| (day, i % side, i / side)).collect(),
+                }
+            }
+            CalendarLayout::Ordered => {
+                let rows = count.div_ceil(side).max(1);
+                let tiles = order.into_iter().zip(0..);
+                Placement {
+                    rows,
+                    columns: side,
+                    tiles: tiles.map(|(day, i)| (day, i / side, i % side)).collect(),
+                }
+            }
+            CalendarLayout::List => Placement {
+                rows: count.max(1),
+                columns: 1,
+                tiles: order
+                    .into_iter()
+                    .map(|day| (day, count - day as u16, 0))
+                    .collect(),
+            },
+            CalendarLayout::Spiral => Placement {
+                rows: side,
+                columns: side,
+                tiles: order
+                    .into_iter()
+                    .zip(spiral(side))
+                    .map(|(day, (row, column))| (day, row, column))
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Every cell of a `side` by `side` grid, clockwise from the top left and inwards
+fn spiral(side: u16) -> Vec<(u16, u16)> {
+    let mut cells = Vec::new();
+    let (mut top, mut left, mut bottom, mut right) = (0, 0, side, side);
+    while top < bottom && left < right {
+        cells.extend((left..right).map(|column| (top, column)));
+        cells.extend((top + 1..bottom).map(|row| (row, right - 1)));
+        if top + 1 < bottom {
+            cells.extend((left..right - 1).rev().map(|column| (bottom - 1, column)));
+        }
+        if left + 1 < right {
+            cells.extend((top + 1..bottom - 1).rev().map(|row| (row, left)));
+        }
+        top += 1;
+        left += 1;
+        bottom -= 1;
+        right -= 1;
+    }
+    cells
+}
+
+/// Trigger to pick a new [`Seed`], which reshuffles the calendar
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RerollSeed;
+
+fn set_layout(
+    layout: On<CalendarLayout>,
+    mut current: ResMut<CalendarLayout>,
+    mut pkv: ResMut<SaveStore>,
+    mut commands: Commands,
+) {
+    *current = *layout;
+    if let Err(e) = pkv.set(PKVKeys::CalendarLayout, &*current) {
+        error!("Failed to save calendar layout: {}", e);
+    }
+    commands.run_system_cached(respawn_calendar);
+}
+
+fn reroll_seed(
+    _: On<RerollSeed>,
+    mut seed: ResMut<Seed>,
+    mut pkv: ResMut<SaveStore>,
+    mut commands: Commands,
+) {
+    seed.reroll(&mut pkv);
+    commands.run_system_cached(respawn_calendar);
+}
+
+fn respawn_calendar(world: &mut World) {
+    if *world.resource::<State<Day>>().get() != Day(0) {
+        return;
+    }
+    let old = world
+        .query_filtered::<Entity, Or<(With<CalendarGrid>, With<DayDetails>)>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in old {
+        world.despawn(entity);
+    }
+    _ = world.run_system_cached(spawn_calendar);
+    _ = world.run_system_cached(update_layout_buttons);
+    _ = world.run_system_cached(update_year_buttons);
+}
+
+#[derive(Component)]
+struct CalendarGrid;
+
+#[derive(Component)]
+pub(crate) struct CalendarTile;
+
+#[derive(Component)]
+struct LayoutButton(CalendarLayout);
+
+fn spawn_calendar_controls(mut commands: Commands, layout: Res<CalendarLayout>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                column_gap: Val::Px(6.0),
+                ..Default::default()
+            },
+            DespawnOnExit(Day(0)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|p| {
+            for option in CalendarLayout::ALL {
+                p.spawn(button(
+                    ButtonProps {
+                        variant: layout_variant(option, *layout),
+                        ..Default::default()
+                    },
+                    LayoutButton(option),
+                    Spawn(Text::new(option.name())),
+                ))
+                .observe(
+                    |event: On<bevy::ui_widgets::Activate>,
+                     buttons: Query<&LayoutButton>,
+                     mut commands: Commands| {
+                        if let Ok(button) = buttons.get(event.entity) {
+                            commands.trigger(button.0);
+                        }
+                    },
+                );
+            }
+            p.spawn(button(
+                ButtonProps::default(),
+                (),
+                Spawn(Text::new("Reroll")),
+            ))
+            .observe(
+                |_: On<bevy::ui_widgets::Activate>, mut commands: Commands| {
+                    commands.trigger(RerollSeed);
+                },
+            );
+        });
+}
+
+fn layout_variant(option: CalendarLayout, current: CalendarLayout) -> ButtonVariant {
+    if option == current {
+        ButtonVariant::Primary
+    } else {
+        ButtonVariant::Normal
+    }
+}
+
+fn update_layout_buttons(
+    layout: Res<CalendarLayout>,
+    mut buttons: Query<(&LayoutButton, &mut ButtonVariant)>,
+) {
+    for (button, mut variant) in &mut buttons {
+        variant.set_if_neq(layout_variant(button.0, *layout));
     }
 }
 
+#[derive(Component)]
+struct YearButton(i32);
+
+/// The years to pick from, in front of the calendar
+fn spawn_year_picker(mut commands: Commands, years: Res<Years>, year: Res<Year>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(6.0),
+                ..Default::default()
+            },
+            Pickable::IGNORE,
+            DespawnOnExit(Day(0)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|p| {
+            for option in years.0.values() {
+                p.spawn(button(
+                    ButtonProps {
+                        variant: year_variant(option.year, year.year),
+                        ..Default::default()
+                    },
+                    YearButton(option.year),
+                    Spawn(Text::new(option.year.to_string())),
+                ))
+                .observe(
+                    |event: On<bevy::ui_widgets::Activate>,
+                     buttons: Query<&YearButton>,
+                     mut commands: Commands| {
+                        if let Ok(button) = buttons.get(event.entity) {
+                            commands.trigger(SelectYear(button.0));
+                        }
+                    },
+                );
+            }
+        });
+}
+
+fn year_variant(option: i32, current: i32) -> ButtonVariant {
+    if option == current {
+        ButtonVariant::Primary
+    } else {
+        ButtonVariant::Normal
+    }
+}
+
+fn update_year_buttons(year: Res<Year>, mut buttons: Query<(&YearButton, &mut ButtonVariant)>) {
+    for (button, mut variant) in &mut buttons {
+        variant.set_if_neq(year_variant(button.0, year.year));
+    }
+}
+
+#[derive(Component)]
+struct DayDetails;
+
 #[derive(Serialize, Deserialize, Clone, Copy)]
 struct DayState {
     puzzle1_completed: bool,
@@ -65,32 +470,35 @@ fn spawn_calendar(
     mut commands: Commands,
     calendar_state: Res<CalendarState>,
     assets: Res<AssetServer>,
-    seed: Res<Seed>,
+    (seed, layout): (Res<Seed>, Res<CalendarLayout>),
+    history: Res<SubmissionHistory>,
+    (policy, titles, year): (Res<UnlockPolicy>, Res<DayTitles>, Res<Year>),
+    mut nav_map: ResMut<DirectionalNavigationMap>,
 ) {
-    let today = chrono::Utc::now().day() as u8;
-    let mut days = (1..=25).collect::<Vec<_>>();
-    days.shuffle(&mut rand::rngs::StdRng::seed_from_u64(seed.0));
+    let now = chrono::Utc::now();
+    let mut tiles = HashMap::new();
+    let placement = layout.place(calendar_state.days.len() as u8, seed.0);
+    // gaps take the same share of the grid however many tiles there are
+    let gap = |tracks: u16| Val::Percent(16. / tracks.saturating_sub(1).max(1) as f32);
     commands
         .spawn((
             Node {
                 height: Val::Percent(80.0),
                 margin: UiRect::all(Val::Auto),
                 display: Display::Grid,
-                aspect_ratio: Some(1.),
-                flex_direction: FlexDirection::Row,
-                grid_auto_flow: GridAutoFlow::Column,
-                flex_wrap: FlexWrap::Wrap,
+                aspect_ratio: Some(placement.columns as f32 / placement.rows as f32),
                 justify_content: JustifyContent::SpaceAround,
                 align_content: AlignContent::SpaceEvenly,
-                row_gap: Val::Percent(4.),
-                column_gap: Val::Percent(4.),
+                row_gap: gap(placement.rows),
+                column_gap: gap(placement.columns),
                 padding: UiRect::all(Val::Percent(2.)),
-                grid_template_rows: vec![RepeatedGridTrack::percent(5, 16.)],
-                grid_template_columns: vec![RepeatedGridTrack::percent(5, 16.)],
+                grid_template_rows: vec![RepeatedGridTrack::fr(placement.rows, 1.)],
+                grid_template_columns: vec![RepeatedGridTrack::fr(placement.columns, 1.)],
                 ..Default::default()
             },
             BorderRadius::all(Val::Px(15.)),
             DespawnOnExit(Day(0)),
+            CalendarGrid,
             BackgroundGradient(vec![Gradient::Linear(LinearGradient::to_bottom_right(
                 vec![
                     ColorStop::new(Color::hsl(0., 0.7, 0.4), Val::Percent(0.0)),
@@ -101,7 +509,7 @@ fn spawn_calendar(
             TabGroup::new(0),
         ))
         .with_children(|p| {
-            for day in days {
+            for &(day, row, column) in &placement.tiles {
                 let color = if calendar_state.days[(day - 1) as usize].puzzle2_completed {
                     Color::linear_rgb(0.827, 0.69, 0.216)
                 } else if calendar_state.days[(day - 1) as usize].puzzle1_completed {
@@ -123,33 +531,274 @@ fn spawn_calendar(
                         },
                         BackgroundColor(Color::hsl(0., 0.7, 0.4)),
                     ),
-                    Spawn((Text::from(format!("{}", day)),)),
+                    (
+                        Spawn((Text::from(format!("{}", day)),)),
+                        Spawn((
+                            Text::new(solved_by(&history, day)),
+                            TextFont::from_font_size(10.),
+                        )),
+                    ),
                 ));
                 button.observe(change_day);
                 button.insert((
                     Node {
                         width: Val::Auto,
                         height: Val::Auto,
+                        grid_row: GridPlacement::start(row as i16 + 1),
+                        grid_column: GridPlacement::start(column as i16 + 1),
+                        flex_direction: FlexDirection::Column,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
                         ..Default::default()
                     },
                     BorderRadius::all(Val::Px(15.)),
                     TabIndex(day as i32),
+                    CalendarTile,
                 ));
-                if day > today || day > 7 {
+                if !titles.contains(year.year, day)
+                    || !policy
+                        .is_unlocked(year.year, day, now, |day| calendar_state.first_star(day))
+                {
                     button.insert(InteractionDisabled);
                 }
+                tiles.insert((row, column), button.id());
             }
         });
+    nav_map.clear();
+    for (&(row, column), &tile) in &tiles {
+        if let Some(&right) = tiles.get(&(row, column + 1)) {
+            nav_map.add_symmetrical_edge(tile, right, CompassOctant::East);
+        }
+        if let Some(&below) = tiles.get(&(row + 1, column)) {
+            nav_map.add_symmetrical_edge(tile, below, CompassOctant::South);
+        }
+    }
 }
 
-fn change_day(event: On<bevy::ui_widgets::Activate>, day: Query<&Day>, mut commands: Commands) {
+/// Arrow keys and the d-pad move between tiles, A on a gamepad opens the focused day
+fn navigate_calendar(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut nav: DirectionalNavigation,
+    mut focus_visible: ResMut<InputFocusVisible>,
+    tiles: Query<(Entity, Has<InteractionDisabled>), With<CalendarTile>>,
+    existing: Query<()>,
+    mut commands: Commands,
+) {
+    let pressed = |key: KeyCode, button: GamepadButton| {
+        keys.just_pressed(key) || gamepads.iter().any(|pad| pad.just_pressed(button))
+    };
+    let direction = [
+        (
+            KeyCode::ArrowUp,
+            GamepadButton::DPadUp,
+            CompassOctant::North,
+        ),
+        (
+            KeyCode::ArrowDown,
+            GamepadButton::DPadDown,
+            CompassOctant::South,
+        ),
+        (
+            KeyCode::ArrowLeft,
+            GamepadButton::DPadLeft,
+            CompassOctant::West,
+        ),
+        (
+            KeyCode::ArrowRight,
+            GamepadButton::DPadRight,
+            CompassOctant::East,
+        ),
+    ]
+    .into_iter()
+    .find(|(key, button, _)| pressed(*key, *button))
+    .map(|(_, _, direction)| direction);
+    if let Some(direction) = direction {
+        focus_visible.0 = true;
+        // nothing focused yet, or the focus was left on a tile from before the day was opened
+        if nav.focus.0.is_none_or(|focus| !existing.contains(focus)) {
+            let top_left = tiles.iter().map(|(tile, _)| tile).find(|tile| {
+                nav.map.get_neighbor(*tile, CompassOctant::North).is_none()
+                    && nav.map.get_neighbor(*tile, CompassOctant::West).is_none()
+            });
+            if let Some(tile) = top_left {
+                nav.focus.set(tile);
+            }
+        } else {
+            _ = nav.navigate(direction);
+        }
+    }
+    if gamepads
+        .iter()
+        .any(|pad| pad.just_pressed(GamepadButton::South))
+        && let Some(focus) = nav.focus.0
+        && let Ok((tile, false)) = tiles.get(focus)
+    {
+        commands.trigger(bevy::ui_widgets::Activate { entity: tile });
+    }
+}
+
+/// Escape or B on a gamepad goes back to the calendar from a day
+fn back_to_calendar(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut next: ResMut<NextState<Day>>,
+) {
+    if keys.just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|pad| pad.just_pressed(GamepadButton::East))
+    {
+        next.set(Day(0));
+    }
+}
+
+/// Which provider confirmed each star, one line per part
+fn solved_by(history: &SubmissionHistory, day: u8) -> String {
+    [Puzzle::Part1, Puzzle::Part2]
+        .into_iter()
+        .filter_map(|puzzle| history.solved_by(day, puzzle))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+type DetailsTile = (
+    Entity,
+    &'static Day,
+    &'static Hovered,
+    &'static UiGlobalTransform,
+    &'static ComputedNode,
+    Has<InteractionDisabled>,
+);
+
+/// Shows the details of the hovered tile, or the focused one when navigating with the keyboard
+fn show_day_details(
+    tiles: Query<DetailsTile, With<CalendarTile>>,
+    (focus, focus_visible): (Res<InputFocus>, Res<InputFocusVisible>),
+    window: Single<&Window>,
+    (calendar, history, dir): (Res<CalendarState>, Res<SubmissionHistory>, Res<InputDir>),
+    (titles, year): (Res<DayTitles>, Res<Year>),
+    mut shown: Local<Option<(Entity, Entity)>>,
+    mut commands: Commands,
+) {
+    let target = tiles.iter().find(|tile| tile.2.get()).or_else(|| {
+        let focused = focus.0.filter(|_| focus_visible.0)?;
+        tiles.get(focused).ok()
+    });
+    let target_entity = target.as_ref().map(|tile| tile.0);
+    if shown.map(|(tile, _)| tile) == target_entity {
+        return;
+    }
+    if let Some((_, details)) = shown.take() {
+        commands.entity(details).try_despawn();
+    }
+    let Some((tile, day, _, transform, node, locked)) = target else {
+        return;
+    };
+    let scale = node.inverse_scale_factor();
+    let center = transform.translation * scale;
+    let size = node.size() * scale;
+    // open on whichever side of the tile has more room
+    let (left, right) = if center.x < window.width() / 2. {
+        (Val::Px(center.x + size.x / 2. + 10.), Val::Auto)
+    } else {
+        let right = window.width() - (center.x - size.x / 2.) + 10.;
+        (Val::Auto, Val::Px(right))
+    };
+    let title = titles.get(year.year, day.0);
+    let lines = day_details(day.0, title, &calendar, &history, &dir, locked);
+    let details = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left,
+                right,
+                top: Val::Px(center.y - size.y / 2.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.),
+                padding: UiRect::all(Val::Px(12.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(8.)),
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(4),
+            Pickable::IGNORE,
+            DespawnOnExit(Day(0)),
+            DayDetails,
+            Children::spawn(SpawnIter(lines.into_iter().enumerate().map(|(i, line)| {
+                let size = if i == 0 { 18. } else { 14. };
+                (Text::new(line), TextFont::from_font_size(size), ThemedText)
+            }))),
+        ))
+        .id();
+    *shown = Some((tile, details));
+}
+
+fn day_details(
+    day: u8,
+    title: Option<&str>,
+    calendar: &CalendarState,
+    history: &SubmissionHistory,
+    dir: &InputDir,
+    locked: bool,
+) -> Vec<String> {
+    let mut lines = vec![match title {
+        Some(title) => format!("Day {}: {}", day, title),
+        None => format!("Day {}", day),
+    }];
+    if locked {
+        lines.push("Locked".to_string());
+    }
+    let state = calendar.days.get(day as usize - 1);
+    for (puzzle, solved) in [
+        (
+            Puzzle::Part1,
+            state.is_some_and(|day| day.puzzle1_completed),
+        ),
+        (
+            Puzzle::Part2,
+            state.is_some_and(|day| day.puzzle2_completed),
+        ),
+    ] {
+        let attempts = history.attempts(day, puzzle).len();
+        let mut line = format!(
+            "{}: {}, {} attempt{}",
+            puzzle,
+            if solved { "solved" } else { "not solved" },
+            attempts,
+            if attempts == 1 { "" } else { "s" }
+        );
+        if let Some(time) = history.first_solved(day, puzzle) {
+            let time = time.with_timezone(&chrono::Local);
+            line.push_str(&format!(", first solved {}", time.format("%Y-%m-%d %H:%M")));
+        }
+        lines.push(line);
+    }
+    let input = match std::fs::read_to_string(dir.path(day, REAL_INPUT)) {
+        Ok(input) if !input.trim().is_empty() => "found",
+        Ok(_) => "empty",
+        Err(_) => "missing",
+    };
+    lines.push(format!("Input: {}", input));
+    lines
+}
+
+fn change_day(
+    event: On<bevy::ui_widgets::Activate>,
+    day: Query<&Day>,
+    reset_mode: Option<Res<crate::reset::ResetMode>>,
+    mut commands: Commands,
+) {
     let Ok(day) = day.get(event.entity) else {
         warn!("Change day observer add to entity without Day component");
         return;
     };
-    println!("Changing to day {:?}", day);
+    if reset_mode.is_some() {
+        commands.queue(crate::reset::ask_to_reset(Some(day.0)));
+        return;
+    }
+    debug!("Changing to day {:?}", day);
     commands.trigger(*day);
 }
 
@@ -193,3 +842,27 @@ fn spawn_back_to_calendar(mut commands: Commands, assets: Res<AssetServer>) {
             },
         );
 }
+
+#[test]
+fn layouts_fit_any_calendar() {
+    for days in [12, 25] {
+        for layout in CalendarLayout::ALL {
+            let placement = layout.place(days, 7);
+            let mut cells = placement
+                .tiles
+                .iter()
+                .map(|(_, row, column)| (*row, *column))
+                .collect::<Vec<_>>();
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), days as usize);
+            assert!(
+                cells
+                    .iter()
+                    .all(|(row, column)| *row < placement.rows && *column < placement.columns)
+            );
+        }
+    }
+    let spiral = CalendarLayout::Spiral.place(25, 0);
+    assert!(spiral.tiles.contains(&(25, 2, 2)));
+}
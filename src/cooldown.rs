@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PKVKeys, Puzzle, Year,
+    save::{Versioned, load_or_report},
+};
+
+/// How long to lock a puzzle after a wrong answer, like AoC does.
+///
+/// Off by default, insert this with `enabled: true` to turn it on
+#[derive(Resource, Debug, Clone)]
+pub struct CooldownPolicy {
+    pub enabled: bool,
+    /// Lockout after each of the first `free_wrong` wrong answers
+    pub base: Duration,
+    /// Wrong answers before the lockout starts doubling
+    pub free_wrong: u32,
+    pub max: Duration,
+}
+
+impl Default for CooldownPolicy {
+    fn default() -> Self {
+        CooldownPolicy {
+            enabled: false,
+            base: Duration::from_secs(60),
+            free_wrong: 3,
+            max: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+impl CooldownPolicy {
+    /// The lockout after the `wrong`th wrong answer, a lockout too long to count is `max`
+    pub fn cooldown(&self, wrong: u32) -> Duration {
+        let doublings = wrong.saturating_sub(self.free_wrong).min(16);
+        self.base
+            .checked_mul(1 << doublings)
+            .map_or(self.max, |cooldown| cooldown.min(self.max))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lockout {
+    pub wrong: u32,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DayCooldown {
+    part1: Lockout,
+    part2: Lockout,
+}
+
+impl DayCooldown {
+    fn get_mut(&mut self, puzzle: Puzzle) -> &mut Lockout {
+        match puzzle {
+            Puzzle::Part1 => &mut self.part1,
+            Puzzle::Part2 => &mut self.part2,
+        }
+    }
+}
+
+/// Wrong answer counts and lockouts for each day and part of the open [`Year`], kept under [`PKVKeys::Cooldowns`]
+#[derive(Resource, Debug, Serialize, Deserialize)]
+pub struct Cooldowns {
+    days: BTreeMap<u8, DayCooldown>,
+}
+
+impl Versioned for Cooldowns {
+    const VERSION: u32 = 1;
+}
+
+impl FromWorld for Cooldowns {
+    fn from_world(world: &mut World) -> Self {
+        let key = world.resource::<Year>().key(PKVKeys::Cooldowns);
+        load_or_report(world, key).unwrap_or_else(|| Cooldowns {
+            days: BTreeMap::new(),
+        })
+    }
+}
+
+impl Cooldowns {
+    /// How long until this puzzle takes answers again, `None` if it isn't locked
+    pub fn remaining(&self, day: u8, puzzle: Puzzle) -> Option<Duration> {
+        let day = self.days.get(&day)?;
+        let lockout = match puzzle {
+            Puzzle::Part1 => &day.part1,
+            Puzzle::Part2 => &day.part2,
+        };
+        (lockout.until? - Utc::now()).to_std().ok()
+    }
+
+    /// Keeps the longest lockout and the most wrong answers of each puzzle
+    pub fn merge(&mut self, other: Cooldowns) {
+        for (day, theirs) in other.days {
+            let ours = self.days.entry(day).or_default();
+            for (ours, theirs) in [
+                (&mut ours.part1, theirs.part1),
+                (&mut ours.part2, theirs.part2),
+            ] {
+                ours.wrong = ours.wrong.max(theirs.wrong);
+                ours.until = ours.until.max(theirs.until);
+            }
+        }
+    }
+
+    /// Forgets the wrong answers and lockout of a puzzle
+    pub fn clear(&mut self, day: u8, puzzle: Puzzle) {
+        if let Some(day) = self.days.get_mut(&day) {
+            *day.get_mut(puzzle) = Lockout::default();
+        }
+    }
+
+    /// Counts a wrong answer and starts the lockout the policy gives for it
+    pub fn wrong_answer(&mut self, policy: &CooldownPolicy, day: u8, puzzle: Puzzle) -> Duration {
+        let lockout = self.days.entry(day).or_default().get_mut(puzzle);
+        lockout.wrong += 1;
+        let cooldown = policy.cooldown(lockout.wrong);
+        lockout.until = chrono::Duration::from_std(cooldown)
+            .ok()
+            .map(|cooldown| Utc::now() + cooldown);
+        cooldown
+    }
+}
+
+/// Formats a cooldown as `1m 05s`
+pub fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    format!("{}m {:02}s", secs / 60, secs % 60)
+}
+
+#[test]
+fn cooldowns_grow() {
+    let policy = CooldownPolicy::default();
+    assert_eq!(policy.cooldown(1), Duration::from_secs(60));
+    assert_eq!(policy.cooldown(3), Duration::from_secs(60));
+    assert_eq!(policy.cooldown(4), Duration::from_secs(120));
+    assert_eq!(policy.cooldown(100), policy.max);
+    let huge = CooldownPolicy {
+        base: Duration::MAX / 2,
+        max: Duration::MAX,
+        ..Default::default()
+    };
+    assert_eq!(huge.cooldown(5), Duration::MAX);
+    assert_eq!(format_remaining(Duration::from_millis(64_500)), "1m 05s");
+}
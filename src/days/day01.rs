@@ -2,30 +2,78 @@ use std::f32::consts::PI;
 
 use bevy::prelude::*;
 
-use crate::{
-    Day, Submit,
-    book_keeping::{Anwsers, CurrentDayRaw},
-    days::Compute,
-};
-
-const DAY: usize = 1;
-
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        // Setup for Day 1 goes here
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
-        app.add_systems(Update, (spin_dial.after(send_message_per_frame),));
+use crate::{AnswerValue, AocDay, Day, ParseReport, in_year};
+
+const DAY: u8 = 1;
+
+pub struct Day01;
+impl AocDay for Day01 {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Secret Entrance";
+    type Input = Vec<i32>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Vec<i32> {
+        parse_input(input, report)
+    }
+
+    fn part1(steps: &Vec<i32>) -> AnswerValue {
+        let mut zeros = 0;
+        let mut index = 50;
+        for step in steps {
+            index += step;
+            index %= 100;
+            if index == 0 {
+                zeros += 1;
+            }
+        }
+        zeros.into()
+    }
+
+    fn part2(steps: &Vec<i32>) -> AnswerValue {
+        let mut index: i32 = 50;
+        let mut passed = 0;
+        for step in steps {
+            for _ in 0..step.abs() {
+                index += step.signum();
+                index %= 100;
+                if index == 0 {
+                    passed += 1;
+                }
+            }
+        }
+        // info!("Part 2: Final position: {}", passed);
+        passed.into()
+    }
+
+    fn spawn_visuals(steps: &Vec<i32>, commands: &mut Commands) {
+        commands.insert_resource(Steps {
+            list: steps.clone(),
+            taken: 0,
+            per_frame: true,
+        });
+    }
+
+    fn cleanup(commands: &mut Commands) {
+        commands.remove_resource::<Steps>();
+    }
+
+    fn build(app: &mut App) {
+        // the dial needs the window, so it's a system rather than part of spawn_visuals
+        app.add_systems(OnEnter(Day(DAY)), spawn_dial.run_if(in_year(Self::YEAR)));
+        app.add_systems(
+            Update,
+            spin_dial
+                .after(send_message_per_frame)
+                .run_if(in_year(Self::YEAR)),
+        );
         app.add_systems(
             FixedUpdate,
             (
                 send_message_per_frame.run_if(resource_exists::<Steps>),
                 update_dial,
             )
-                .chain(),
+                .chain()
+                .run_if(in_year(Self::YEAR)),
         );
         app.add_observer(send_steps);
 
@@ -82,96 +130,27 @@ fn send_steps(
 #[derive(Component)]
 struct Arrow;
 
-fn parse_input(input: &str) -> Steps {
+fn parse_input(input: &str, report: &mut ParseReport) -> Vec<i32> {
     let mut steps = Vec::new();
     for line in input.lines() {
-        if let Ok(step) = line.trim()[1..].parse::<i32>() {
-            if line.starts_with('R') {
-                steps.push(step);
-            } else if line.starts_with('L') {
-                steps.push(-step);
-            } else {
-                error!("Invalid direction in line: {}", line);
-            }
-        } else {
-            error!("Failed to parse line: {}", line);
-        }
-    }
-    Steps {
-        list: steps,
-        taken: 0,
-        per_frame: true,
-    }
-}
-
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
-    commands.insert_resource(parse_input(&input.0));
-    commands.trigger(Compute::<DAY>);
-}
-
-fn cleanup_day(mut commands: Commands) {
-    commands.remove_resource::<Steps>();
-}
-
-fn solve_part1(
-    _: On<Compute<DAY>>,
-    steps: Res<Steps>,
-    mut answers: ResMut<Anwsers>,
-    mut commands: Commands,
-) {
-    let mut zeros = 0;
-    let mut index = 50;
-    for step in steps.iter() {
-        index += step;
-        index %= 100;
-        if index == 0 {
-            zeros += 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
         }
-    }
-    answers.add(DAY, crate::state::Puzzle::Part1, zeros);
-}
-
-fn solve_part2(
-    _: On<Compute<DAY>>,
-    steps: Res<Steps>,
-    mut answers: ResMut<Anwsers>,
-    mut commands: Commands,
-) {
-    let mut index: i32 = 50;
-    let mut index2 = 50;
-    let mut passed = 0;
-    let mut passed2 = 0;
-    for (i, step) in steps.iter().enumerate() {
-        // count the number of times we pass zero this lap
-        let full = step.abs() / 100;
-        // add the number of full laps to zeros passed
-        passed2 += full;
-
-        // store if we are left or right of zero before the step
-        let is_pos = index.is_positive();
-        index2 += step % 100;
-        if index2.is_positive() != is_pos {
-            passed2 += 1;
-        }
-        if index2 == 0 {
-            passed2 += 1;
-        } else if index2 > 99 {
-            passed2 += 1;
-            index2 -= 100;
-        } else if index2 < 0 {
-            passed2 += 1;
-            index2 += 100;
-        }
-        for _ in 0..step.abs() {
-            index += step.signum();
-            index %= 100;
-            if index == 0 {
-                passed += 1;
-            }
+        let mut chars = line.chars();
+        let direction = chars.next();
+        let distance = chars.as_str();
+        let Ok(step) = distance.parse::<i32>() else {
+            report.error(input, distance, "Expected a distance after the direction");
+            continue;
+        };
+        match direction {
+            Some('R') => steps.push(step),
+            Some('L') => steps.push(-step),
+            _ => report.error(input, line, "Expected the direction to be L or R"),
         }
     }
-    // info!("Part 2: Final position: {}", passed);
-    answers.add(DAY, crate::state::Puzzle::Part2, passed);
+    steps
 }
 
 fn send_message_per_frame(mut message_writer: MessageWriter<Step>, mut steps: ResMut<Steps>) {
@@ -182,7 +161,7 @@ fn send_message_per_frame(mut message_writer: MessageWriter<Step>, mut steps: Re
     }
 }
 
-fn spawn_visuals(
+fn spawn_dial(
     window: Single<&Window>,
     mut commands: Commands,
     mut meshs: ResMut<Assets<Mesh>>,
@@ -190,9 +169,7 @@ fn spawn_visuals(
     asset_server: Res<AssetServer>,
 ) {
     let size = window.width().min(window.height());
-    let circle = meshs.add(Circle::new(size * 0.33));
     let arrow = meshs.add(Triangle2d::default());
-    let silver = colors.add(Color::linear_rgb(0.71, 0.718, 0.733));
     let red = colors.add(Color::linear_rgb(0.827, 0.216, 0.216));
     commands.spawn((
         Dial {
@@ -204,11 +181,11 @@ fn spawn_visuals(
             custom_size: Some(Vec2::splat(0.6 * size)),
             ..Default::default()
         },
-        DespawnOnExit(Day(DAY as u8)),
+        DespawnOnExit(Day(DAY)),
         Transform::from_rotation(Quat::from_rotation_z(PI)),
     ));
     commands.spawn((
-        DespawnOnExit(Day(DAY as u8)),
+        DespawnOnExit(Day(DAY)),
         Mesh2d(arrow),
         MeshMaterial2d(red),
         Arrow,
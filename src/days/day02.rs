@@ -2,25 +2,37 @@ use std::ops::RangeInclusive;
 
 use bevy::prelude::*;
 
-use crate::{
-    Day,
-    book_keeping::{Anwsers, CurrentDayRaw},
-    days::Compute,
-};
-const DAY: usize = 2;
+use crate::{AnswerValue, AocDay, Day, ParseReport};
+const DAY: u8 = 2;
 
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
+pub struct Day02;
+impl AocDay for Day02 {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Gift Shop";
+    type Input = Vec<RangeInclusive<usize>>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Self::Input {
+        parse_input(input, report)
+    }
+
+    fn part1(ranges: &Self::Input) -> AnswerValue {
+        solve_part1(ranges).into()
+    }
+
+    fn part2(ranges: &Self::Input) -> AnswerValue {
+        solve_part2(ranges).into()
+    }
+
+    fn spawn_visuals(ranges: &Self::Input, commands: &mut Commands) {
+        let root = commands.spawn(DespawnOnExit(Day(DAY))).id();
+        for range in ranges {
+            commands.spawn((
+                ChildOf(root),
+                Product,
+                children![ProductId(*range.start()), ProductId(*range.end())],
+            ));
+        }
     }
-}
-#[derive(Resource)]
-struct Products {
-    ranges: Vec<RangeInclusive<usize>>,
 }
 
 #[derive(Component)]
@@ -34,43 +46,35 @@ impl ProductId {
     }
 }
 
-fn parse_input(input: &str, commands: &mut Commands) {
+fn parse_input(input: &str, report: &mut ParseReport) -> Vec<RangeInclusive<usize>> {
     let mut ranges = Vec::new();
-    let root = commands.spawn(DespawnOnExit(Day(DAY as u8))).id();
     for line in input.split(',') {
-        let (a, b) = line.split_once('-').unwrap();
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((a, b)) = line.split_once('-') else {
+            report.error(input, line, "Expected a range like 11-22");
+            continue;
+        };
         let Ok(a) = a.parse() else {
-            error!("Failed to parse range start: {}", a);
+            report.error(input, a, "Failed to parse range start");
             continue;
         };
         let Ok(b) = b.parse() else {
-            error!("Failed to parse range end: {}", b);
+            report.error(input, b, "Failed to parse range end");
             continue;
         };
         ranges.push(a..=b);
-        commands.spawn((
-            ChildOf(root),
-            Product,
-            children![ProductId(a), ProductId(b)],
-        ));
     }
-    commands.insert_resource(Products { ranges });
+    ranges
 }
 
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
-    parse_input(&input.0, &mut commands);
-    commands.trigger(Compute::<DAY>);
-}
-
-fn cleanup_day(mut commands: Commands) {
-    commands.remove_resource::<Products>();
-}
-
-fn solve_part1(_: On<Compute<DAY>>, products: Res<Products>, mut answers: ResMut<Anwsers>) {
+fn solve_part1(ranges: &[RangeInclusive<usize>]) -> usize {
     let mut invalid = 0;
     // use a buffer to avoid a new string every loop?
     let mut rep = String::new();
-    for range in &products.ranges {
+    for range in ranges {
         for id in range.clone() {
             rep.clear();
             use std::fmt::write;
@@ -85,14 +89,14 @@ fn solve_part1(_: On<Compute<DAY>>, products: Res<Products>, mut answers: ResMut
             }
         }
     }
-    answers.add(DAY, crate::state::Puzzle::Part1, invalid as u64);
+    invalid
 }
 
-fn solve_part2(_: On<Compute<DAY>>, products: Res<Products>, mut answers: ResMut<Anwsers>) {
+fn solve_part2(ranges: &[RangeInclusive<usize>]) -> usize {
     let mut invalid = 0;
     // use a buffer to avoid a new string every loop?
     let mut rep = String::new();
-    for range in &products.ranges {
+    for range in ranges {
         for id in range.clone() {
             rep.clear();
             use std::fmt::write;
@@ -116,7 +120,5 @@ fn solve_part2(_: On<Compute<DAY>>, products: Res<Products>, mut answers: ResMut
             }
         }
     }
-    answers.add(DAY, crate::state::Puzzle::Part2, invalid as u64);
+    invalid
 }
-
-fn spawn_visuals(mut commands: Commands) {}
@@ -3,24 +3,36 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{
-    Day,
-    book_keeping::{Anwsers, CurrentDayRaw},
-    days::Compute,
-};
-const DAY: usize = 3;
+use crate::{AnswerValue, AocDay, Day, ParseReport, in_year};
+const DAY: u8 = 3;
+
+pub struct Day03;
+impl AocDay for Day03 {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Lobby";
+    type Input = Vec<Vec<Battery>>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Self::Input {
+        parse_input(input, report)
+    }
 
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
+    fn part1(banks: &Self::Input) -> AnswerValue {
+        solve_part1(banks).into()
+    }
 
+    fn part2(banks: &Self::Input) -> AnswerValue {
+        solve_part2(banks).into()
+    }
+
+    fn spawn_visuals(banks: &Self::Input, commands: &mut Commands) {
+        spawn_banks(banks, commands);
+    }
+
+    fn build(app: &mut App) {
         app.add_systems(
             Update,
-            (draw_number_line, init_12_slot, scan).run_if(in_state(Day(DAY as u8))),
+            (draw_number_line, init_12_slot, scan)
+                .run_if(in_state(Day(DAY)).and(in_year(Self::YEAR))),
         );
     }
 }
@@ -129,11 +141,14 @@ impl Bank {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Battery>> {
+fn parse_input(input: &str, report: &mut ParseReport) -> Vec<Vec<Battery>> {
     let mut banks = Vec::new();
     for line in input.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
         let mut bank = Vec::new();
-        for ch in line.chars() {
+        for (i, ch) in line.char_indices() {
             let battery = match ch {
                 '1' => Battery::P1,
                 '2' => Battery::P2,
@@ -144,7 +159,15 @@ fn parse_input(input: &str) -> Vec<Vec<Battery>> {
                 '7' => Battery::P7,
                 '8' => Battery::P8,
                 '9' => Battery::P9,
-                _ => continue,
+                ch if ch.is_whitespace() => continue,
+                _ => {
+                    report.error(
+                        input,
+                        &line[i..i + ch.len_utf8()],
+                        "Expected a battery level from 1 to 9",
+                    );
+                    continue;
+                }
             };
             bank.push(battery);
         }
@@ -153,7 +176,7 @@ fn parse_input(input: &str) -> Vec<Vec<Battery>> {
     banks
 }
 
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
+fn spawn_banks(banks: &[Vec<Battery>], commands: &mut Commands) {
     let bank_node = Node {
         width: Val::Percent(100.),
         height: Val::Percent(100.),
@@ -171,11 +194,11 @@ fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
         align_items: AlignItems::Stretch,
         ..Default::default()
     };
-    let banks = parse_input(&input.0).into_iter().map(|bank| {
+    let banks = banks.iter().map(|bank| {
         (
             BackgroundColor(Color::Srgba(bevy::color::palettes::css::TAN).lighter(0.25)),
             BorderRadius::all(Val::Px(15.)),
-            Children::spawn(bank),
+            Children::spawn(bank.clone()),
             Bank,
             bank_node.clone(),
         )
@@ -194,7 +217,7 @@ fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
             BorderColor::all(Color::BLACK),
             BorderRadius::all(Val::Px(15.0)),
             BackgroundColor(Color::Srgba(bevy::color::palettes::css::TAN).lighter(0.30)),
-            DespawnOnExit(Day(DAY as u8)),
+            DespawnOnExit(Day(DAY)),
             children![
                 (
                     Node {
@@ -242,26 +265,16 @@ fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
                 });
             });
         });
-    commands.trigger(Compute::<DAY>);
 }
 
-fn cleanup_day(mut commands: Commands) {}
-
-fn solve_part1(
-    _: On<Compute<DAY>>,
-    input: Query<&Children, With<Bank>>,
-    batterys: Query<&Battery>,
-    mut answers: ResMut<Anwsers>,
-) {
+fn solve_part1(banks: &[Vec<Battery>]) -> usize {
     let mut total_power = 0;
-    for bank in &input {
+    for bank in banks {
         let mut b0 = 0;
-        let mut b1 = batterys.get(*bank.last().unwrap()).unwrap().level();
-        for battery in 0..bank.len() {
-            let power = batterys
-                .get(bank[battery])
-                .expect("Battery in Bank")
-                .level();
+        let Some(mut b1) = bank.last().map(Battery::level) else {
+            continue;
+        };
+        for (battery, power) in bank.iter().map(Battery::level).enumerate() {
             if power > b0 && battery != bank.len() - 1 {
                 b0 = power;
                 b1 = 0;
@@ -271,22 +284,17 @@ fn solve_part1(
         }
         total_power += b0 * 10 + b1;
     }
-    answers.add(DAY, crate::state::Puzzle::Part1, total_power as u64);
+    total_power
 }
 
-fn solve_part2(
-    _: On<Compute<DAY>>,
-    input: Query<&Children, With<Bank>>,
-    batterys: Query<&Battery>,
-    mut answers: ResMut<Anwsers>,
-) {
+fn solve_part2(banks: &[Vec<Battery>]) -> usize {
     let mut total_power = 0;
     let mut used_batterys = [0; 12];
-    for bank in &input {
+    for bank in banks {
         used_batterys.fill(0);
         let mut left = bank.len();
-        for battery in bank.iter() {
-            let power = batterys.get(battery).expect("Battery in Bank").level();
+        for battery in bank {
+            let power = battery.level();
             let mut zero = false;
             for used in used_batterys
                 .iter_mut()
@@ -308,10 +316,8 @@ fn solve_part2(
             total_power += used_batterys[11 - i] * 10_usize.pow(i as u32);
         }
     }
-    answers.add(DAY, crate::state::Puzzle::Part2, total_power as u64);
+    total_power
 }
-
-fn spawn_visuals(mut commands: Commands) {}
 // 17406 low
 // 173904601285724 low
 
@@ -334,7 +340,7 @@ fn draw_number_line(
                     ..default()
                 },
                 BackgroundColor(Color::linear_rgb(0., 0., 0.)),
-                DespawnOnExit(Day(DAY as u8)),
+                DespawnOnExit(Day(DAY)),
             ))
             .id();
         let root = commands
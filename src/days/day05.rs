@@ -1,19 +1,33 @@
-use bevy::{platform::collections::HashSet, prelude::*};
-
-use crate::{
-    Day,
-    book_keeping::{Anwsers, CurrentDayRaw},
-    days::Compute,
-};
-const DAY: usize = 5;
-
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
+use bevy::prelude::*;
+
+use crate::{AnswerValue, AocDay, Day, ParseReport};
+const DAY: u8 = 5;
+
+pub struct Day05;
+impl AocDay for Day05 {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Cafeteria";
+    type Input = FreshList;
+
+    fn parse(input: &str, report: &mut ParseReport) -> FreshList {
+        parse_input(input, report)
+    }
+
+    fn part1(fresh: &FreshList) -> AnswerValue {
+        solve_part1(fresh).into()
+    }
+
+    fn part2(fresh: &FreshList) -> AnswerValue {
+        solve_part2(fresh).into()
+    }
+
+    fn spawn_visuals(fresh: &FreshList, commands: &mut Commands) {
+        let ingredients = fresh
+            .ingredients
+            .iter()
+            .map(|ing| (Ingredient(*ing), DespawnOnExit(Day(DAY))))
+            .collect::<Vec<_>>();
+        commands.spawn_batch(ingredients);
     }
 }
 
@@ -23,9 +37,10 @@ struct Fresh {
     end: usize,
 }
 
-#[derive(Resource, Debug)]
+#[derive(Debug)]
 pub struct FreshList {
     list: Vec<Fresh>,
+    ingredients: Vec<usize>,
 }
 
 impl FreshList {
@@ -43,61 +58,63 @@ impl FreshList {
 #[derive(Component)]
 pub struct Ingredient(pub usize);
 
-fn parse_input(input: &str, commands: &mut Commands) {
+fn parse_input(input: &str, report: &mut ParseReport) -> FreshList {
     let mut fresh = Vec::new();
-    for line in input.lines() {
+    let mut lines = input.lines();
+    for line in lines.by_ref() {
+        let line = line.trim();
         if line.is_empty() {
             break;
         }
-        let (start, end) = line.split_once('-').unwrap();
+        let Some((start, end)) = line.split_once('-') else {
+            report.error(input, line, "Expected a fresh range like 3-5");
+            continue;
+        };
         let Ok(start) = start.parse() else {
-            error!("Failed to parse fresh start: {}", start);
+            report.error(input, start, "Failed to parse fresh start");
             continue;
         };
         let Ok(end) = end.parse() else {
-            error!("Failed to parse fresh end: {}", end);
+            report.error(input, end, "Failed to parse fresh end");
             continue;
         };
         fresh.push(Fresh { start, end });
     }
-    fresh.sort_by(|a, b| a.start.cmp(&b.start));
-    for line in input.lines().skip(fresh.len() + 1) {
+    if fresh.is_empty() && report.is_clean() {
+        report.error_at(1, 1, "", "Expected at least one fresh range");
+    }
+    fresh.sort_by_key(|fresh| fresh.start);
+    let mut ingredients = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
         let Ok(ing) = line.parse() else {
-            error!("Failed to parse ingredient: {}", line);
+            report.error(input, line, "Failed to parse ingredient");
             continue;
         };
-        commands.spawn(Ingredient(ing));
+        ingredients.push(ing);
+    }
+    FreshList {
+        list: fresh,
+        ingredients,
     }
-
-    commands.insert_resource(FreshList { list: fresh });
-}
-
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
-    parse_input(&input.0, &mut commands);
-    commands.trigger(Compute::<DAY>);
 }
 
-fn cleanup_day(mut commands: Commands) {}
-
-fn solve_part1(
-    _: On<Compute<DAY>>,
-    fresh: Res<FreshList>,
-    query: Query<&Ingredient>,
-    mut answers: ResMut<Anwsers>,
-) {
+fn solve_part1(fresh: &FreshList) -> usize {
     let mut fresh_ing = 0;
     // info!("{:?}", fresh);
-    for Ingredient(ing) in &query {
+    for ing in &fresh.ingredients {
         if fresh.is_fresh(*ing) {
             fresh_ing += 1;
         }
     }
-
-    answers.add(DAY, crate::state::Puzzle::Part1, fresh_ing as u64);
     // info!("Part 1: {fresh_ing}");
+    fresh_ing
 }
 
-fn solve_part2(_: On<Compute<DAY>>, fresh: Res<FreshList>, mut answers: ResMut<Anwsers>) {
+fn solve_part2(fresh: &FreshList) -> usize {
     let mut dedup_fresh = vec![fresh.list[0], *fresh.list.last().unwrap()];
     'out: for fresh in &fresh.list[1..] {
         for dedup in &mut dedup_fresh {
@@ -132,10 +149,8 @@ fn solve_part2(_: On<Compute<DAY>>, fresh: Res<FreshList>, mut answers: ResMut<A
     //     fresh.list.len(),
     //     dedup_fresh.len()
     // );
-    answers.add(DAY, crate::state::Puzzle::Part2, total_fresh as u64);
     // info!("Part 2: {}", total_fresh);
+    total_fresh
 }
-
-fn spawn_visuals(mut commands: Commands) {}
 // 329989062881448 low
 // 358155203664116
@@ -1,31 +1,54 @@
 use bevy::prelude::*;
 
-use crate::{
-    Day,
-    book_keeping::{Anwsers, CurrentDayRaw},
-    days::Compute,
-};
-const DAY: usize = 6;
+use crate::{AnswerValue, AocDay, ParseReport};
+const DAY: u8 = 6;
 
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
+pub struct Day06;
+impl AocDay for Day06 {
+    const DAY: u8 = DAY;
+    const TITLE: &'static str = "Trash Compactor";
+    type Input = Worksheet;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Worksheet {
+        parse_input(input, report)
+    }
+
+    fn part1(worksheet: &Worksheet) -> AnswerValue {
+        let total = worksheet
+            .problems
+            .iter()
+            .map(Problem::compute)
+            .sum::<usize>();
+        total.into()
+    }
+
+    fn part2(worksheet: &Worksheet) -> AnswerValue {
+        let mut total = 0;
+        for problem in &worksheet.cephalopod {
+            // println!(
+            //     "{:?} {} = {}",
+            //     problem.numbers,
+            //     problem.operation,
+            //     problem.compute()
+            // );
+            total += problem.compute();
+        }
+        // info!("Part 2: {}", total);
+        total.into()
     }
 }
 
-#[derive(Component)]
+/// The problems read left to right, and read the cephalopod way down the columns
+pub struct Worksheet {
+    problems: Vec<Problem>,
+    cephalopod: Vec<Problem>,
+}
+
 struct Problem {
     numbers: Vec<usize>,
     operation: Operation,
 }
 
-#[derive(Component)]
-struct Cephalopod;
-
 impl Problem {
     fn push(&mut self, value: usize) {
         self.numbers.push(value);
@@ -58,46 +81,55 @@ impl std::fmt::Display for Operation {
     }
 }
 
-fn parse_input(input: &str, commands: &mut Commands) {
-    let mut problems: Vec<(Problem, DespawnOnExit<Day>)> = Vec::new();
+fn parse_input(input: &str, report: &mut ParseReport) -> Worksheet {
+    let mut problems = Vec::new();
     for line in input.lines() {
         for (i, token) in line.split_whitespace().enumerate() {
             if problems.len() <= i {
-                problems.push((
-                    Problem {
-                        numbers: Vec::new(),
-                        operation: Operation::Add,
-                    },
-                    DespawnOnExit(Day(DAY as u8)),
-                ));
+                problems.push(Problem {
+                    numbers: Vec::new(),
+                    operation: Operation::Add,
+                });
             }
             if token == "+" {
-                problems[i].0.set_add();
+                problems[i].set_add();
                 continue;
             }
             if token == "*" {
-                problems[i].0.set_multiply();
+                problems[i].set_multiply();
                 continue;
             }
             if let Ok(value) = token.parse() {
-                problems[i].0.push(value);
+                problems[i].push(value);
                 continue;
             }
+            report.error(input, token, "Expected a number, + or *");
         }
     }
-    commands.spawn_batch(problems);
 
-    let mut lines = input.lines().map(|l| l.chars()).collect::<Vec<_>>();
+    // the line number of each row, so errors point at the line the bad cell is on
+    let (line_numbers, rows): (Vec<_>, Vec<_>) = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line))
+        .unzip();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+        report.error(
+            input,
+            row,
+            "Every line needs the same length, part 2 reads down the columns",
+        );
+    }
+    let mut lines = rows.iter().map(|l| l.chars()).collect::<Vec<_>>();
     let mut column = Vec::new();
     let mut block: Vec<Vec<char>> = vec![Vec::new(); lines.len()];
-    loop {
-        let Some(next) = lines
-            .iter_mut()
-            .map(|c| c.next())
-            .collect::<Option<Vec<_>>>()
-        else {
-            break;
-        };
+    while let Some(next) = lines
+        .iter_mut()
+        .map(|c| c.next())
+        .collect::<Option<Vec<_>>>()
+    {
         if next.iter().all(|c| c == &' ') {
             let mut new = vec![Vec::new(); lines.len()];
             std::mem::swap(&mut block, &mut new);
@@ -109,9 +141,16 @@ fn parse_input(input: &str, commands: &mut Commands) {
         }
     }
     column.push(block);
-    let mut problems = Vec::new();
+    let mut cephalopod = Vec::new();
+    let mut offset = 0;
     for mut col in column {
-        let op = if col.pop().unwrap().contains(&'+') {
+        let width = col.first().map_or(0, Vec::len);
+        let start = offset + 1;
+        offset += width + 1;
+        let Some(op) = col.pop() else {
+            continue;
+        };
+        let op = if op.contains(&'+') {
             Operation::Add
         } else {
             Operation::Multiply
@@ -121,56 +160,39 @@ fn parse_input(input: &str, commands: &mut Commands) {
             operation: op,
         };
         let mut value = String::new();
-        for column in 0..col[0].len() {
+        for column in 0..width {
             value.clear();
             for row in col.iter() {
                 value.push(row[column]);
             }
-            problem.push(value.trim().parse().unwrap());
+            match value.trim().parse() {
+                Ok(value) => problem.push(value),
+                Err(_) => report.error_at(
+                    line_numbers[value
+                        .chars()
+                        .position(|c| !c.is_ascii_digit() && c != ' ')
+                        .unwrap_or(0)],
+                    start + column,
+                    &value,
+                    "Expected a number reading down this column",
+                ),
+            }
         }
-        problems.push((problem, Cephalopod, DespawnOnExit(Day(DAY as u8))));
+        cephalopod.push(problem);
     }
-    commands.spawn_batch(problems);
-}
-
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
-    parse_input(&input.0, &mut commands);
-    commands.trigger(Compute::<DAY>);
-}
-
-fn cleanup_day(mut commands: Commands) {}
-
-fn solve_part1(
-    _: On<Compute<DAY>>,
-    problems: Query<&Problem, Without<Cephalopod>>,
-    mut answers: ResMut<Anwsers>,
-) {
-    let mut total = 0;
-    for problem in &problems {
-        total += problem.compute();
-    }
-    answers.add(DAY, crate::state::Puzzle::Part1, total as u64);
-    // info!("Part 1: {}", total);
-}
-
-fn solve_part2(
-    _: On<Compute<DAY>>,
-    problems: Query<&Problem, With<Cephalopod>>,
-    mut answers: ResMut<Anwsers>,
-) {
-    let mut total = 0;
-    for problem in &problems {
-        // println!(
-        //     "{:?} {} = {}",
-        //     problem.numbers,
-        //     problem.operation,
-        //     problem.compute()
-        // );
-        total += problem.compute();
+    Worksheet {
+        problems,
+        cephalopod,
     }
-    answers.add(DAY, crate::state::Puzzle::Part2, total as u64);
-    // info!("Part 2: {}", total);
 }
-fn spawn_visuals(mut commands: Commands) {}
 
 // 11159825692437 low
+
+#[test]
+fn column_errors_point_at_their_line() {
+    let input = "12 3\n4x 5\n*  +\n";
+    let mut report = ParseReport::new(DAY, input);
+    parse_input(input, &mut report);
+    assert!(!report.is_clean());
+    assert!(report.issues.iter().all(|issue| issue.line == 2));
+}
@@ -1,33 +1,31 @@
 use bevy::prelude::*;
 
-use crate::{Day, book_keeping::CurrentDayRaw, days::Solve};
-const DAY: usize = 0;
-
-pub struct DayPlugin;
-impl Plugin for DayPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(Day(DAY as u8)), (setup_day, spawn_visuals));
-        app.add_observer(solve_part1);
-        app.add_observer(solve_part2);
-        app.add_systems(OnExit(Day(DAY as u8)), cleanup_day);
+use crate::{AnswerValue, AocDay, ParseReport};
+const DAY: u8 = 0;
+
+/// Copy to `dayNN.rs`, then add it in `DaysPlugin` with `app.add_day::<DayNN>()`
+pub struct DayNN;
+impl AocDay for DayNN {
+    const DAY: u8 = DAY;
+    const YEAR: i32 = crate::EVENT_YEAR;
+    const TITLE: &'static str = "";
+    type Input = Vec<String>;
+
+    fn parse(input: &str, _report: &mut ParseReport) -> Vec<String> {
+        input.lines().map(String::from).collect()
     }
-}
 
-fn parse_input(input: &str, commands: &mut Commands) {}
-
-fn setup_day(input: Res<CurrentDayRaw>, mut commands: Commands) {
-    parse_input(&input.0, &mut commands);
-    commands.trigger(Solve::<DAY>);
-}
+    fn part1(_input: &Vec<String>) -> AnswerValue {
+        info!("Part 1: Not Solved");
+        0.into()
+    }
 
-fn cleanup_day(mut commands: Commands) {}
+    fn part2(_input: &Vec<String>) -> AnswerValue {
+        info!("Part 2: Not Solved");
+        0.into()
+    }
 
-fn solve_part1(_: On<Solve<DAY>>) {
-    info!("Part 1: Not Solved");
-}
+    fn spawn_visuals(_input: &Vec<String>, _commands: &mut Commands) {}
 
-fn solve_part2(_: On<Solve<DAY>>) {
-    info!("Part 2: Not Solved");
+    fn cleanup(_commands: &mut Commands) {}
 }
-
-fn spawn_visuals(mut commands: Commands) {}
@@ -8,8 +8,9 @@ use bevy::{
 struct RawDay(String);
 
 impl FromWorld for RawDay {
-    fn from_world(_world: &mut World) -> Self {
-        RawDay(include_str!("../../assets/days/day03.input").to_string())
+    fn from_world(world: &mut World) -> Self {
+        let dir = world.resource::<crate::InputDir>();
+        RawDay(std::fs::read_to_string(dir.path(3, crate::REAL_INPUT)).unwrap_or_default())
     }
 }
 
@@ -89,20 +90,6 @@ impl Battery {
         ]);
     }
 
-    fn level(&self) -> usize {
-        match self {
-            Battery::P1 => 1,
-            Battery::P2 => 2,
-            Battery::P3 => 3,
-            Battery::P4 => 4,
-            Battery::P5 => 5,
-            Battery::P6 => 6,
-            Battery::P7 => 7,
-            Battery::P8 => 8,
-            Battery::P9 => 9,
-        }
-    }
-
     fn color(&self) -> Color {
         match self {
             Battery::P1 => Color::linear_rgb(0.1, 0.01, 0.01),
@@ -1,28 +1,182 @@
-use bevy::prelude::*;
+use std::{collections::BTreeMap, marker::PhantomData};
 
-use crate::state::Day;
+use bevy::{platform::time::Instant, prelude::*};
+
+use crate::{
+    AddYear, AnswerValue, Day, EVENT_YEAR, ParseReport, Puzzle, Solver, Step, Timing, Year, Years,
+    book_keeping::{Anwsers, CurrentDayRaw},
+    in_year,
+    timing::SolveClock,
+};
 
 pub struct DaysPlugin;
 
 impl Plugin for DaysPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(crate::book_keeping::update_day);
-        app.add_plugins(day01::DayPlugin);
-        app.add_plugins(day02::DayPlugin);
-        app.add_plugins(day03::DayPlugin);
-        app.add_plugins(day04::DayPlugin);
-        app.add_plugins(day05::DayPlugin);
-        app.add_plugins(day06::DayPlugin);
-        app.add_plugins(day07::DayPlugin);
+        app.add_year(Year::aoc(EVENT_YEAR));
+        // no 2024 days yet, its calendar is for days that set `const YEAR: i32 = 2024`
+        app.add_year(Year::aoc(2024));
+        app.add_day::<day01::Day01>();
+        app.add_day::<day02::Day02>();
+        app.add_day::<day03::Day03>();
+        app.add_day::<day04::Day04>();
+        app.add_day::<day05::Day05>();
+        app.add_day::<day06::Day06>();
+        app.add_day::<day07::Day07>();
+    }
+}
+
+/// A day's puzzle, implement this then add it with [`AddDay::add_day`]
+pub trait AocDay: Send + Sync + 'static {
+    const DAY: u8;
+    /// The event the day is from
+    const YEAR: i32 = EVENT_YEAR;
+    /// The puzzle's title, shown on the calendar
+    const TITLE: &'static str = "";
+    /// What the input is parsed into, kept in [`DayInput`] while the day is open
+    type Input: Send + Sync + 'static;
+
+    /// Problems with the input go in `report`, the parts are only solved if it stays clean
+    fn parse(input: &str, report: &mut ParseReport) -> Self::Input;
+    fn part1(input: &Self::Input) -> AnswerValue;
+    fn part2(input: &Self::Input) -> AnswerValue;
+
+    /// Spawns what the day shows, anything spawned should be [`DespawnOnExit`] the day
+    fn spawn_visuals(_input: &Self::Input, _commands: &mut Commands) {}
+    /// Runs when the day is closed
+    fn cleanup(_commands: &mut Commands) {}
+    /// Adds any other systems the day needs, give every one `run_if(in_year(Self::YEAR))`
+    /// so they don't run for another year's day with the same number
+    fn build(_app: &mut App) {}
+}
+
+/// Titles of the days that have been added, by year and day
+#[derive(Resource, Default)]
+pub struct DayTitles(pub BTreeMap<(i32, u8), &'static str>);
+
+impl DayTitles {
+    pub fn get(&self, year: i32, day: u8) -> Option<&'static str> {
+        self.0
+            .get(&(year, day))
+            .copied()
+            .filter(|title| !title.is_empty())
     }
+
+    /// Whether the day has been added
+    pub fn contains(&self, year: i32, day: u8) -> bool {
+        self.0.contains_key(&(year, day))
+    }
+}
+
+/// The parsed input of the open day
+pub struct DayInput<T: AocDay>(pub T::Input);
+
+impl<T: AocDay> Resource for DayInput<T> {}
+
+impl<T: AocDay> std::ops::Deref for DayInput<T> {
+    type Target = T::Input;
+
+    fn deref(&self) -> &T::Input {
+        &self.0
+    }
+}
+
+/// Parses and solves a day when it's opened, and tidies up when it's closed
+pub struct DayPlugin<T: AocDay>(PhantomData<fn() -> T>);
+
+impl<T: AocDay> Default for DayPlugin<T> {
+    fn default() -> Self {
+        DayPlugin(PhantomData)
+    }
+}
+
+impl<T: AocDay> Plugin for DayPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(Day(T::DAY)),
+            setup_day::<T>.run_if(in_year(T::YEAR)),
+        );
+        app.add_systems(
+            OnExit(Day(T::DAY)),
+            cleanup_day::<T>.run_if(in_year(T::YEAR)),
+        );
+        app.world_mut()
+            .get_resource_or_init::<DayTitles>()
+            .0
+            .insert((T::YEAR, T::DAY), T::TITLE);
+        app.world_mut()
+            .get_resource_or_init::<Years>()
+            .0
+            .entry(T::YEAR)
+            .or_insert(Year::aoc(T::YEAR));
+        T::build(app);
+    }
+}
+
+pub trait AddDay {
+    fn add_day<T: AocDay>(&mut self) -> &mut Self;
+}
+
+impl AddDay for App {
+    fn add_day<T: AocDay>(&mut self) -> &mut Self {
+        self.add_plugins(DayPlugin::<T>::default())
+    }
+}
+
+fn setup_day<T: AocDay>(
+    raw: Res<CurrentDayRaw>,
+    mut answers: ResMut<Anwsers>,
+    mut commands: Commands,
+) {
+    let mut timed = |step, start: Instant| {
+        commands.trigger(Timing {
+            day: T::DAY,
+            step,
+            solver: Solver::Reference,
+            duration: start.elapsed(),
+        });
+    };
+    let mut report = ParseReport::new(T::DAY, &raw.0);
+    let start = Instant::now();
+    let input = T::parse(&raw.0, &mut report);
+    timed(Step::Parse, start);
+    if report.is_clean() {
+        let start = Instant::now();
+        let part1 = T::part1(&input);
+        timed(Step::Part1, start);
+        let start = Instant::now();
+        let part2 = T::part2(&input);
+        timed(Step::Part2, start);
+        answers.add(T::DAY as usize, Puzzle::Part1, part1);
+        answers.add(T::DAY as usize, Puzzle::Part2, part2);
+    }
+    T::spawn_visuals(&input, &mut commands);
+    commands.insert_resource(DayInput::<T>(input));
+    commands.insert_resource(SolveClock::start(T::DAY));
+    commands.trigger(report);
+}
+
+fn cleanup_day<T: AocDay>(mut commands: Commands) {
+    commands.remove_resource::<DayInput<T>>();
+    T::cleanup(&mut commands);
 }
 
 #[test]
 fn gen_day_files() {
+    let dir = crate::InputDir(std::env::temp_dir().join("aob_gen_day_files"));
+    _ = std::fs::remove_dir_all(&dir.0);
+    dir.create_missing(25).unwrap();
+    std::fs::write(dir.path(1, crate::REAL_INPUT), "R10").unwrap();
+    dir.create_missing(25).unwrap();
     for day in 1..=25 {
-        let day_path = format!("assets/days/day{:02}.input", day);
-        std::fs::write(day_path, "").unwrap();
+        assert!(dir.path(day, crate::REAL_INPUT).exists());
     }
+    assert_eq!(
+        std::fs::read_to_string(dir.path(1, crate::REAL_INPUT)).unwrap(),
+        "R10"
+    );
+    _ = std::fs::remove_dir_all(&dir.0);
 }
 
 pub mod day01;
@@ -34,5 +188,3 @@ pub mod day06;
 pub mod day07;
 
 pub mod day_s;
-#[derive(Event)]
-struct Compute<const DAY: usize>;
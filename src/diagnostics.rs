@@ -0,0 +1,195 @@
+use std::fmt;
+
+use bevy::{
+    feathers::{
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    prelude::*,
+};
+
+use crate::{Day, toast::TOAST_ERROR};
+
+/// How many issues the panel lists before summarising the rest
+const MAX_SHOWN: usize = 12;
+
+/// Shows the diagnostics panel when a day's input didn't parse cleanly
+pub struct DiagnosticsPlugin;
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn_diagnostics_panel);
+    }
+}
+
+/// A problem in a puzzle input, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIssue {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}:{} {} `{}`",
+            self.line, self.column, self.message, self.snippet
+        )
+    }
+}
+
+/// Everything wrong with a day's input, every `parse_input` returns one instead of panicking.
+///
+/// Triggered once parsing is done and kept as a resource until the next day is parsed
+#[derive(Event, Resource, Debug, Clone, Default)]
+pub struct ParseReport {
+    pub day: u8,
+    pub issues: Vec<ParseIssue>,
+}
+
+impl ParseReport {
+    /// Starts a report for `input`, an empty input is already an issue
+    pub fn new(day: u8, input: &str) -> ParseReport {
+        let mut report = ParseReport {
+            day,
+            issues: Vec::new(),
+        };
+        if input.trim().is_empty() {
+            report.error_at(1, 1, "", "Input is empty");
+        }
+        report
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Reports a problem with `part`, which should be a slice of `input` so the line and column can be worked out,
+    /// anything else is reported at the start of the input
+    pub fn error(&mut self, input: &str, part: &str, message: impl Into<String>) {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| input.is_char_boundary(*offset))
+            .unwrap_or(0);
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line = input[..offset].matches('\n').count() + 1;
+        let column = input[line_start..offset].chars().count() + 1;
+        self.error_at(line, column, &input[line_start..line_end], message);
+    }
+
+    pub fn error_at(
+        &mut self,
+        line: usize,
+        column: usize,
+        snippet: &str,
+        message: impl Into<String>,
+    ) {
+        let mut snippet = snippet.trim_end().to_string();
+        if snippet.chars().count() > 60 {
+            snippet = snippet.chars().take(57).chain("...".chars()).collect();
+        }
+        self.issues.push(ParseIssue {
+            line,
+            column,
+            snippet,
+            message: message.into(),
+        });
+    }
+}
+
+/// Logs the issues and keeps the report around, part of the core so headless runs see it too
+pub(crate) fn store_parse_report(report: On<ParseReport>, mut commands: Commands) {
+    for issue in &report.issues {
+        error!("Day {} input: {}", report.day, issue);
+    }
+    commands.insert_resource(report.clone());
+}
+
+#[derive(Component)]
+struct DiagnosticsPanel;
+
+fn spawn_diagnostics_panel(
+    report: On<ParseReport>,
+    old: Query<Entity, With<DiagnosticsPanel>>,
+    mut commands: Commands,
+) {
+    for panel in &old {
+        commands.entity(panel).despawn();
+    }
+    if report.is_clean() {
+        return;
+    }
+    let mut lines = vec![format!(
+        "Day {} input has {} problem{}, answers were not computed (click to close)",
+        report.day,
+        report.issues.len(),
+        if report.issues.len() == 1 { "" } else { "s" }
+    )];
+    lines.extend(
+        report
+            .issues
+            .iter()
+            .take(MAX_SHOWN)
+            .map(ToString::to_string),
+    );
+    if report.issues.len() > MAX_SHOWN {
+        lines.push(format!("...and {} more", report.issues.len() - MAX_SHOWN));
+    }
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.),
+                left: Val::Px(10.),
+                max_width: Val::Percent(60.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.),
+                padding: UiRect::all(Val::Px(12.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(8.)),
+            ThemeBackgroundColor(TOAST_ERROR),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(5),
+            DespawnOnExit(Day(report.day)),
+            DiagnosticsPanel,
+            Children::spawn(SpawnIter(lines.into_iter().map(|line| {
+                (Text::new(line), TextFont::from_font_size(14.), ThemedText)
+            }))),
+        ))
+        .observe(|click: On<Pointer<Click>>, mut commands: Commands| {
+            commands.entity(click.entity).despawn();
+        });
+}
+
+#[test]
+fn parse_report_positions() {
+    let input = "R10\nL5x\n\nR3";
+    let mut report = ParseReport::new(1, input);
+    report.error(input, &input[6..7], "Not a number");
+    assert_eq!(
+        report.issues[0],
+        ParseIssue {
+            line: 2,
+            column: 3,
+            snippet: "L5x".to_string(),
+            message: "Not a number".to_string(),
+        }
+    );
+    assert!(!ParseReport::new(1, " \n").is_clean());
+    let input = "é1\nx";
+    let mut report = ParseReport::new(1, input);
+    report.error(input, &String::from("elsewhere"), "Not from the input");
+    assert!(
+        report
+            .issues
+            .iter()
+            .all(|issue| issue.line == 1 && issue.column == 1)
+    );
+}
@@ -0,0 +1,264 @@
+use bevy::{input::InputPlugin, prelude::*, state::app::StatesPlugin};
+use serde::Serialize;
+
+use crate::{
+    AnswerValue, CorePlugin, Day, ImportMode, InputDir, InputRoot, ParseReport, Puzzle, REAL_INPUT,
+    SaveFile, StepTimes, StoreBackend, Timings, Year, Years, book_keeping::Anwsers,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Runs the reference solver of every day in the last opened year on its input without opening a window,
+/// then prints the answers
+#[derive(Default)]
+pub struct HeadlessPlugin {
+    pub format: OutputFormat,
+    /// Same as [`AoCSettings::input_dir`](crate::AoCSettings::input_dir)
+    pub input_dir: Option<std::path::PathBuf>,
+    /// Exports or imports progress instead of running the days
+    pub transfer: Option<Transfer>,
+    /// Same as [`AoCSettings::store`](crate::AoCSettings::store)
+    pub store: StoreBackend,
+}
+
+#[derive(Debug, Clone)]
+pub enum Transfer {
+    Export(std::path::PathBuf),
+    /// Asks whether to merge or overwrite when `mode` is `None`
+    Import {
+        path: std::path::PathBuf,
+        mode: Option<ImportMode>,
+    },
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        let input_root = InputRoot::resolve(self.input_dir.clone());
+        input_root.register_asset_source(app);
+        // the input plugin is only here so day systems that read the keyboard still validate
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            StatesPlugin,
+            InputPlugin,
+        ));
+        app.add_plugins(CorePlugin {
+            input_root,
+            store: self.store.clone(),
+        });
+        let format = self.format;
+        match self.transfer.clone() {
+            Some(transfer) => app.set_runner(move |app| run_transfer(app, transfer)),
+            None => app.set_runner(move |app| run_all_days(app, format)),
+        };
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum InputStatus {
+    Found,
+    Empty,
+    Missing,
+}
+
+#[derive(Serialize)]
+struct DayReport {
+    day: u8,
+    input: InputStatus,
+    part1: Option<AnswerValue>,
+    part2: Option<AnswerValue>,
+    /// How long the reference solver took this run
+    micros: StepMicros,
+    issues: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct StepMicros {
+    parse: Option<u128>,
+    part1: Option<u128>,
+    part2: Option<u128>,
+}
+
+impl From<StepTimes> for StepMicros {
+    fn from(times: StepTimes) -> Self {
+        let micros = |time: Option<std::time::Duration>| time.map(|time| time.as_micros());
+        StepMicros {
+            parse: micros(times.parse),
+            part1: micros(times.part1),
+            part2: micros(times.part2),
+        }
+    }
+}
+
+fn run_all_days(mut app: App, format: OutputFormat) -> AppExit {
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let dir = app.world().resource::<InputDir>().clone();
+    let year = *app.world().resource::<Year>();
+    let mut reports = Vec::new();
+    for day in year.days() {
+        let input = match std::fs::read_to_string(dir.path(day, REAL_INPUT)) {
+            Err(_) => InputStatus::Missing,
+            Ok(raw) if raw.trim().is_empty() => InputStatus::Empty,
+            Ok(_) => InputStatus::Found,
+        };
+        // empty inputs are skipped, some reference solvers panic on them
+        if input == InputStatus::Found {
+            app.world_mut().trigger(Day(day));
+            open_day(&mut app, Day(day));
+            app.world_mut().trigger(Day(0));
+            app.update();
+        }
+        let issues = app
+            .world()
+            .get_resource::<ParseReport>()
+            .filter(|report| report.day == day)
+            .map(|report| report.issues.iter().map(ToString::to_string).collect())
+            .unwrap_or_default();
+        let answers = app.world().resource::<Anwsers>();
+        let micros = app
+            .world()
+            .resource::<Timings>()
+            .latest(day)
+            .reference
+            .into();
+        reports.push(DayReport {
+            day,
+            input,
+            part1: answers.get(day, Puzzle::Part1).cloned(),
+            part2: answers.get(day, Puzzle::Part2).cloned(),
+            micros,
+            issues,
+        });
+    }
+
+    match format {
+        OutputFormat::Table => print_table(&reports),
+        OutputFormat::Json => match serde_json::to_string_pretty(&reports) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                error!("Failed to serialize answers: {}", e);
+                return AppExit::error();
+            }
+        },
+    }
+    AppExit::Success
+}
+
+/// Updates until the day has opened, inputs load on the asset server's threads
+fn open_day(app: &mut App, day: Day) {
+    for _ in 0..1000 {
+        app.update();
+        if *app.world().resource::<State<Day>>().get() == day {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    error!("Day {} input didn't load", day.0);
+}
+
+fn run_transfer(mut app: App, transfer: Transfer) -> AppExit {
+    app.finish();
+    app.cleanup();
+    let world = app.world_mut();
+    let local = SaveFile::read(world.resource(), world.resource::<Years>());
+    let result = match transfer {
+        Transfer::Export(path) => local
+            .save(&path)
+            .map(|()| println!("Exported progress to {}", path.display())),
+        Transfer::Import { path, mode } => SaveFile::load(&path).and_then(|file| {
+            let conflicts = file.conflicts(&local, world.resource::<Years>());
+            if conflicts.is_empty() {
+                println!("Nothing here conflicts with {}", path.display());
+            } else {
+                println!("{} conflicts with {}:", conflicts.len(), path.display());
+                for conflict in &conflicts {
+                    println!("  {}", conflict);
+                }
+            }
+            let Some(mode) = mode.or_else(ask_import_mode) else {
+                println!("Import cancelled");
+                return Ok(());
+            };
+            crate::transfer::apply(world, file, mode)
+                .map(|()| println!("Imported progress from {}", path.display()))
+        }),
+    };
+    match result {
+        Ok(()) => AppExit::Success,
+        Err(e) => {
+            error!("{}", e);
+            AppExit::error()
+        }
+    }
+}
+
+fn ask_import_mode() -> Option<ImportMode> {
+    println!("Merge, overwrite or cancel? [m/o/C]");
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).ok()?;
+    match answer.trim().to_lowercase().as_str() {
+        "m" | "merge" => Some(ImportMode::Merge),
+        "o" | "overwrite" => Some(ImportMode::Overwrite),
+        _ => None,
+    }
+}
+
+fn print_table(reports: &[DayReport]) {
+    let show = |answer: &Option<AnswerValue>| {
+        answer
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "-".to_string())
+    };
+    println!("{:>3} | {:<8} | {:<24} | Part 2", "Day", "Input", "Part 1");
+    println!("{:-<3}-+-{:-<8}-+-{:-<24}-+-{:-<24}", "", "", "", "");
+    for report in reports {
+        println!(
+            "{:>3} | {:<8} | {:<24} | {}",
+            report.day,
+            match report.input {
+                InputStatus::Found => "found",
+                InputStatus::Empty => "empty",
+                InputStatus::Missing => "missing",
+            },
+            show(&report.part1),
+            show(&report.part2),
+        );
+    }
+    for report in reports {
+        for issue in &report.issues {
+            println!("Day {} input: {}", report.day, issue);
+        }
+    }
+}
+
+#[test]
+fn memory_saves_stay_in_their_app() {
+    let input_dir = std::env::temp_dir().join("aob_memory_saves_stay_in_their_app");
+    let app = || {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin {
+            input_dir: Some(input_dir.clone()),
+            store: StoreBackend::Memory,
+            ..Default::default()
+        });
+        app
+    };
+    let mut first = app();
+    let second = app();
+    let mut saves = first.world_mut().resource_mut::<crate::SaveStore>();
+    saves.set(crate::PKVKeys::Seed, &7u64).unwrap();
+    assert_eq!(saves.get::<u64>(crate::PKVKeys::Seed).unwrap(), 7);
+    let saves = second.world().resource::<crate::SaveStore>();
+    assert!(saves.get::<u64>(crate::PKVKeys::Seed).is_err());
+    _ = std::fs::remove_dir_all(&input_dir);
+}
@@ -0,0 +1,555 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    feathers::controls::{ButtonProps, ButtonVariant, button},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    Anwsers, Day, EVENT_YEAR, KeyEntry, MissingInput, MissingReason, Toast,
+    book_keeping::CurrentDayRaw,
+};
+
+/// The input progress counts for, `dayNN/real.input` or the older `dayNN.input`
+pub const REAL_INPUT: &str = "real";
+
+/// Picks the input folder when [`AoCSettings::input_dir`](crate::AoCSettings::input_dir) isn't set
+pub const INPUT_DIR_VAR: &str = "AOB_INPUT_DIR";
+
+/// Asset source inputs are loaded from, e.g. `input://2025/day05.input`
+pub const INPUT_SOURCE: &str = "input";
+
+/// Where puzzle inputs are kept, each year in its own folder.
+///
+/// AoC asks people not to share their inputs, so this defaults to somewhere outside the repository
+#[derive(Resource, Debug, Clone)]
+pub struct InputRoot(pub PathBuf);
+
+impl InputRoot {
+    /// `dir` if given, then [`INPUT_DIR_VAR`], then an `inputs` folder in the user's data dir
+    pub fn resolve(dir: Option<PathBuf>) -> InputRoot {
+        let dir = dir
+            .or_else(|| std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from))
+            .or_else(|| {
+                directories::ProjectDirs::from("", "Phox", "AoB")
+                    .map(|dirs| dirs.data_dir().join("inputs"))
+            })
+            .unwrap_or_else(|| PathBuf::from("inputs"));
+        // the asset server resolves relative paths from the crate, not the working directory
+        match std::env::current_dir() {
+            Ok(cwd) if dir.is_relative() => InputRoot(cwd.join(dir)),
+            _ => InputRoot(dir),
+        }
+    }
+
+    /// Prepares the folder and loads inputs from it as the [`INPUT_SOURCE`] asset source,
+    /// call before the [`AssetPlugin`] is added
+    pub(crate) fn register_asset_source(&self, app: &mut App) {
+        // the file watcher needs the folder to exist before the asset source is registered
+        if let Err(e) = self.prepare() {
+            error!("Failed to create inputs in {}: {}", self.0.display(), e);
+        }
+        app.register_asset_source(
+            INPUT_SOURCE,
+            bevy::asset::io::AssetSourceBuilder::platform_default(&self.0.to_string_lossy(), None),
+        );
+    }
+
+    /// The folder a year's inputs are in
+    pub fn year(&self, year: i32) -> InputDir {
+        InputDir(self.0.join(year.to_string()))
+    }
+
+    /// Creates the folder, moving inputs from before there were years and from the old `assets/days` folder
+    /// into [`EVENT_YEAR`]'s folder
+    pub fn prepare(&self) -> std::io::Result<()> {
+        let assets = bevy::asset::io::file::FileAssetReader::get_base_path().join("assets/days");
+        self.prepare_from(&assets)
+    }
+
+    fn prepare_from(&self, assets: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.0)?;
+        let year = self.year(EVENT_YEAR);
+        if year.0.exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&year.0)?;
+        for dir in [self.0.as_path(), assets] {
+            // however many days the old calendar had, every `dayNN.input` and `dayNN` folder is moved
+            let names = std::fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| is_day_input(name))
+                .collect::<Vec<_>>();
+            for name in names {
+                let (old, new) = (dir.join(&name), year.0.join(&name));
+                if !new.exists() {
+                    move_input(&old, &new)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `dayNN.input` or a `dayNN` folder of named inputs
+fn is_day_input(name: &str) -> bool {
+    name.strip_prefix("day")
+        .map(|day| day.strip_suffix(".input").unwrap_or(day))
+        .is_some_and(|day| !day.is_empty() && day.parse::<u8>().is_ok())
+}
+
+/// Renames, or copies then removes when `to` is on another drive
+fn move_input(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            move_input(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        std::fs::remove_dir(from)
+    } else {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)
+    }
+}
+
+/// Where the open [`Year`](crate::Year)'s inputs are kept, a folder inside the [`InputRoot`]
+#[derive(Resource, Debug, Clone)]
+pub struct InputDir(pub PathBuf);
+
+impl InputDir {
+    /// Where a named input lives, relative to the input folder
+    pub fn relative_path(&self, day: u8, name: &str) -> String {
+        let named = format!("day{:02}/{}.input", day, name);
+        if name == REAL_INPUT && !self.0.join(&named).exists() {
+            format!("day{:02}.input", day)
+        } else {
+            named
+        }
+    }
+
+    pub fn path(&self, day: u8, name: &str) -> PathBuf {
+        self.0.join(self.relative_path(day, name))
+    }
+
+    /// The path to give the [`AssetServer`], the asset source is the [`InputRoot`]
+    pub fn asset_path(&self, day: u8, name: &str) -> String {
+        let year = self.0.file_name().unwrap_or_default().to_string_lossy();
+        format!(
+            "{}://{}/{}",
+            INPUT_SOURCE,
+            year,
+            self.relative_path(day, name)
+        )
+    }
+
+    /// Creates the folder and an empty real input for each of the first `days` that doesn't have one yet
+    pub fn create_missing(&self, days: u8) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.0)?;
+        for day in 1..=days {
+            let path = self.path(day, REAL_INPUT);
+            if !path.exists() {
+                std::fs::write(path, "")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Names of every input there is for a day, the real input first
+    pub fn day_inputs(&self, day: u8) -> Vec<String> {
+        let mut names = std::fs::read_dir(self.0.join(format!("day{:02}", day)))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "input"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .filter(|name| name != REAL_INPUT)
+            .collect::<Vec<_>>();
+        names.sort();
+        names.insert(0, REAL_INPUT.to_string());
+        names
+    }
+
+    /// The answers saved next to an input in `dayNN/<name>.answers`, e.g. `(part1: 3, part2: 6)`
+    pub fn expected(&self, day: u8, name: &str) -> Option<KeyEntry> {
+        let path = self.0.join(format!("day{:02}/{}.answers", day, name));
+        let text = std::fs::read_to_string(&path).ok()?;
+        crate::providers::ron_options()
+            .from_str(&text)
+            .inspect_err(|e| error!("Failed to parse {}: {}", path.display(), e))
+            .ok()
+    }
+}
+
+/// Which named input each day is using, days that aren't in here use [`REAL_INPUT`]
+#[derive(Resource, Default)]
+pub struct InputSelection {
+    days: HashMap<u8, String>,
+}
+
+impl InputSelection {
+    pub fn get(&self, day: u8) -> &str {
+        self.days.get(&day).map_or(REAL_INPUT, String::as_str)
+    }
+
+    pub fn is_real(&self, day: u8) -> bool {
+        self.get(day) == REAL_INPUT
+    }
+}
+
+/// Trigger to switch the open day to another of its inputs
+#[derive(Event, Debug, Clone)]
+pub struct SelectInput(pub String);
+
+/// Loads `.input` files through the [`AssetServer`], opening a day once its input has loaded
+/// and again when the file is edited on disk
+pub struct PuzzleInputPlugin;
+
+impl Plugin for PuzzleInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PuzzleInput>();
+        app.init_asset_loader::<PuzzleInputLoader>();
+        app.init_resource::<CurrentDayInput>();
+        app.add_systems(Update, apply_day_input);
+        app.add_systems(OnEnter(Day(0)), forget_day_input);
+    }
+}
+
+/// The buttons to switch the open day to another of its inputs
+pub(crate) struct InputSelectorPlugin;
+
+impl Plugin for InputSelectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(select_input);
+        app.add_systems(OnExit(Day(0)), spawn_input_selector);
+    }
+}
+
+/// The raw text of a puzzle input file
+#[derive(Asset, TypePath, Debug)]
+pub struct PuzzleInput(pub String);
+
+#[derive(Default, TypePath)]
+pub struct PuzzleInputLoader;
+
+impl AssetLoader for PuzzleInputLoader {
+    type Asset = PuzzleInput;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<PuzzleInput, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(PuzzleInput(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["input"]
+    }
+}
+
+/// Handle to the open day's input, kept so the file watcher has something to report changes on
+#[derive(Resource, Default)]
+pub(crate) struct CurrentDayInput {
+    handle: Option<Handle<PuzzleInput>>,
+    /// The day to open once the handle has loaded
+    opening: Option<Day>,
+}
+
+impl CurrentDayInput {
+    fn open(&mut self, day: Day, handle: Handle<PuzzleInput>) {
+        self.handle = Some(handle);
+        self.opening = Some(day);
+    }
+
+    /// Starts loading the day's `name` input, or asks for it with [`MissingInput`] when there's no file.
+    /// Empty inputs are caught once loaded, so opening a day and switching its input are checked the same way
+    pub(crate) fn load(
+        &mut self,
+        day: Day,
+        name: &str,
+        dir: &InputDir,
+        asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) -> bool {
+        let path = dir.path(day.0, name);
+        if !path.exists() {
+            warn!("Day {} {} input is {}", day.0, name, MissingReason::Missing);
+            commands.trigger(MissingInput {
+                day,
+                name: name.to_string(),
+                path,
+                reason: MissingReason::Missing,
+            });
+            return false;
+        }
+        self.open(day, asset_server.load(dir.asset_path(day.0, name)));
+        true
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = CurrentDayInput::default();
+    }
+}
+
+#[derive(Component)]
+struct InputButton(String);
+
+fn spawn_input_selector(
+    mut commands: Commands,
+    day: Res<State<Day>>,
+    selection: Res<InputSelection>,
+    dir: Res<InputDir>,
+) {
+    let day = day.0;
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                column_gap: Val::Px(6.0),
+                ..Default::default()
+            },
+            DespawnOnEnter(Day(0)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|p| {
+            for name in dir.day_inputs(day) {
+                let variant = if name == selection.get(day) {
+                    ButtonVariant::Primary
+                } else {
+                    ButtonVariant::Normal
+                };
+                p.spawn(button(
+                    ButtonProps {
+                        variant,
+                        ..Default::default()
+                    },
+                    InputButton(name.clone()),
+                    Spawn(Text::new(name)),
+                ))
+                .observe(
+                    |event: On<bevy::ui_widgets::Activate>,
+                     inputs: Query<&InputButton>,
+                     mut commands: Commands| {
+                        if let Ok(input) = inputs.get(event.entity) {
+                            commands.trigger(SelectInput(input.0.clone()));
+                        }
+                    },
+                );
+            }
+        });
+}
+
+fn select_input(
+    select: On<SelectInput>,
+    day: Res<State<Day>>,
+    mut selection: ResMut<InputSelection>,
+    (mut current, dir): (ResMut<CurrentDayInput>, Res<InputDir>),
+    asset_server: Res<AssetServer>,
+    mut buttons: Query<(&InputButton, &mut ButtonVariant)>,
+    mut commands: Commands,
+) {
+    let day = *day.get();
+    if day.0 == 0 {
+        error!("No day is open to pick an input for");
+        return;
+    }
+    // kept even when the input is missing, so onboarding writes and opens the one that was picked
+    selection.days.insert(day.0, select.0.clone());
+    for (button, mut variant) in &mut buttons {
+        let selected = if button.0 == select.0 {
+            ButtonVariant::Primary
+        } else {
+            ButtonVariant::Normal
+        };
+        variant.set_if_neq(selected);
+    }
+    if current.load(day, &select.0, &dir, &asset_server, &mut commands) {
+        commands.trigger(Toast::info(format!(
+            "Using the {} input for day {}",
+            select.0, day.0
+        )));
+    }
+}
+
+/// Opens the day waiting on its input once it has loaded, first opens and switching inputs both go through here,
+/// then re-enters the day when its input changes so it's parsed and computed again
+fn apply_day_input(
+    mut events: MessageReader<AssetEvent<PuzzleInput>>,
+    mut current: ResMut<CurrentDayInput>,
+    (inputs, asset_server): (Res<Assets<PuzzleInput>>, Res<AssetServer>),
+    (day, mut next): (Res<State<Day>>, ResMut<NextState<Day>>),
+    (selection, dir, mut answers): (Res<InputSelection>, Res<InputDir>, ResMut<Anwsers>),
+    mut commands: Commands,
+) {
+    let Some(handle) = current.handle.clone() else {
+        events.clear();
+        return;
+    };
+    let modified = events.read().any(|event| event.is_modified(&handle));
+    if let Some(opening) = current.opening {
+        let name = selection.get(opening.0);
+        let reason = if asset_server.load_state(&handle).is_failed() {
+            MissingReason::Missing
+        } else {
+            match inputs.get(&handle) {
+                // still loading
+                None => return,
+                Some(input) if input.0.trim().is_empty() => MissingReason::Empty,
+                Some(input) => {
+                    current.opening = None;
+                    answers.real_input = selection.is_real(opening.0);
+                    let raw = CurrentDayRaw(input.0.clone());
+                    if opening == *day.get() {
+                        commands.queue(move |world: &mut World| reenter_day(world, opening, raw));
+                    } else {
+                        commands.insert_resource(raw);
+                        next.set(opening);
+                    }
+                    return;
+                }
+            }
+        };
+        // dropped so the input is read again once onboarding has written it
+        current.clear();
+        warn!("Day {} {} input is {}", opening.0, name, reason);
+        commands.trigger(MissingInput {
+            day: opening,
+            name: name.to_string(),
+            path: dir.path(opening.0, name),
+            reason,
+        });
+        return;
+    }
+    if !modified {
+        return;
+    }
+    let Some(input) = inputs.get(&handle) else {
+        return;
+    };
+    // editors often save an empty file before writing the new contents
+    if input.0.trim().is_empty() {
+        warn!("Day {} input is empty, not reloading", day.0);
+        return;
+    }
+    let raw = CurrentDayRaw(input.0.clone());
+    let day = *day.get();
+    commands.queue(move |world: &mut World| reenter_day(world, day, raw));
+    commands.trigger(Toast::info(format!(
+        "Day {} input changed, reloading",
+        day.0
+    )));
+}
+
+/// However the day was left, so editing its input on the calendar doesn't re-enter the calendar
+fn forget_day_input(mut current: ResMut<CurrentDayInput>) {
+    current.clear();
+}
+
+/// Setting [`NextState`] to the current day is an identity transition,
+/// which skips `OnExit`/`OnEnter`, so they are run here by hand
+fn reenter_day(world: &mut World, day: Day, raw: CurrentDayRaw) {
+    let scoped = world
+        .query::<(Entity, &DespawnOnExit<Day>)>()
+        .iter(world)
+        .filter(|(_, scope)| scope.0 == day)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    for entity in scoped {
+        // may already be gone with a scoped parent
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+    _ = world.try_run_schedule(OnExit(day));
+    world.insert_resource(raw);
+    _ = world.try_run_schedule(OnEnter(day));
+}
+
+#[test]
+fn leaving_a_day_forgets_its_input() {
+    let input_dir = std::env::temp_dir().join("aob_leaving_a_day_forgets_its_input");
+    _ = std::fs::remove_dir_all(&input_dir);
+    let mut app = App::new();
+    app.add_plugins(crate::HeadlessPlugin {
+        input_dir: Some(input_dir.clone()),
+        store: crate::StoreBackend::Memory,
+        ..Default::default()
+    });
+    app.finish();
+    app.cleanup();
+    app.update();
+    let dir = app.world().resource::<InputDir>().clone();
+    std::fs::write(dir.path(1, REAL_INPUT), "R10").unwrap();
+    app.world_mut().trigger(Day(1));
+    for _ in 0..1000 {
+        app.update();
+        if *app.world().resource::<State<Day>>().get() == Day(1) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    assert_eq!(*app.world().resource::<State<Day>>().get(), Day(1));
+    let handle = app.world().resource::<CurrentDayInput>().handle.clone();
+    // like the back button, which sets the state rather than triggering Day(0)
+    app.world_mut().resource_mut::<NextState<Day>>().set(Day(0));
+    app.update();
+    let calendar = app.world_mut().spawn(DespawnOnExit(Day(0))).id();
+    app.world_mut().write_message(AssetEvent::Modified {
+        id: handle.unwrap().id(),
+    });
+    app.update();
+    assert!(app.world().get_entity(calendar).is_ok());
+    assert_eq!(*app.world().resource::<State<Day>>().get(), Day(0));
+    _ = std::fs::remove_dir_all(&input_dir);
+}
+
+#[test]
+fn inputs_move_into_their_year() {
+    let root = InputRoot(std::env::temp_dir().join("aob_inputs_move_into_their_year"));
+    _ = std::fs::remove_dir_all(&root.0);
+    std::fs::create_dir_all(root.0.join("day02")).unwrap();
+    std::fs::write(root.0.join("day01.input"), "R10").unwrap();
+    std::fs::write(root.0.join("day02/example.input"), "11-22").unwrap();
+    let assets = std::env::temp_dir().join("aob_inputs_move_into_their_year_assets");
+    _ = std::fs::remove_dir_all(&assets);
+    std::fs::create_dir_all(&assets).unwrap();
+    std::fs::write(assets.join("day01.input"), "L5").unwrap();
+    std::fs::write(assets.join("day03.input"), "987").unwrap();
+    std::fs::write(assets.join("day25.input"), "").unwrap();
+    std::fs::write(assets.join("notes.txt"), "").unwrap();
+    root.prepare_from(&assets).unwrap();
+    let dir = root.year(EVENT_YEAR);
+    assert_eq!(
+        std::fs::read_to_string(dir.path(1, REAL_INPUT)).unwrap(),
+        "R10"
+    );
+    assert!(dir.path(2, "example").exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.path(3, REAL_INPUT)).unwrap(),
+        "987"
+    );
+    assert!(!assets.join("day03.input").exists());
+    assert!(dir.path(25, REAL_INPUT).exists());
+    assert!(assets.join("notes.txt").exists());
+    assert!(!root.0.join("day01.input").exists());
+    assert_eq!(dir.asset_path(1, REAL_INPUT), "input://2025/day01.input");
+    _ = std::fs::remove_dir_all(&root.0);
+    _ = std::fs::remove_dir_all(&assets);
+}
@@ -6,29 +6,147 @@ mod state;
 
 mod book_keeping;
 
+mod answer;
+
 mod ui;
 
-pub use book_keeping::Submit;
+mod toast;
+
+mod headless;
+
+mod providers;
+
+mod cooldown;
+
+mod input;
+
+mod diagnostics;
+
+mod onboarding;
+
+mod unlock;
+
+mod year;
+
+mod transfer;
+
+mod profile;
+
+mod reset;
+
+mod save;
+
+mod store;
+
+mod timing;
+
+pub use answer::AnswerValue;
+pub use book_keeping::{
+    Anwsers, Attempt, DayHistory, MissingInput, MissingReason, Results, SubmissionHistory, Submit,
+};
+pub use cooldown::{CooldownPolicy, Cooldowns, Lockout};
 pub use days::*;
-pub use state::Day;
+pub use diagnostics::{ParseIssue, ParseReport};
+pub use headless::{HeadlessPlugin, OutputFormat, Transfer};
+pub use input::{
+    INPUT_DIR_VAR, InputDir, InputRoot, InputSelection, PuzzleInput, PuzzleInputLoader, REAL_INPUT,
+    SelectInput,
+};
+pub use profile::{Profile, SaveStore, SelectProfile};
+pub use providers::{
+    AnswerKey, AnswerProvider, HashedAnswer, HashedAnswerKey, KeyEntry, ManualAnswer,
+    ManualAnswers, ReferenceSolvers,
+};
+pub use reset::ResetProgress;
+pub use save::{LoadError, Versioned};
+pub use state::{Day, Puzzle};
+pub use store::{JsonFileStore, MemoryStore, ProgressStore, StoreBackend, StoreError};
+pub use timing::{DayTimings, Solver, Step, StepTimes, Timing, Timings, format_duration};
+pub use toast::{Toast, ToastKind};
+pub use transfer::{ExportProgress, ImportMode, ImportProgress, SAVE_VERSION, SaveFile};
+pub use unlock::{EVENT_YEAR, UnlockPolicy};
+pub use year::{AddYear, SelectYear, Year, Years, in_year};
 
 pub struct AoCPlugin;
 
+/// Insert before adding [`AoCPlugin`] to change where it keeps inputs and saves
+#[derive(Resource, Default, Clone)]
+pub struct AoCSettings {
+    /// Where puzzle inputs are kept, see [`InputRoot::resolve`] for what is used when this is `None`
+    pub input_dir: Option<std::path::PathBuf>,
+    /// Where progress and settings are saved
+    pub store: StoreBackend,
+}
+
 impl Plugin for AoCPlugin {
     fn build(&self, app: &mut App) {
+        let settings = app
+            .world()
+            .get_resource::<AoCSettings>()
+            .cloned()
+            .unwrap_or_default();
+        let input_root = InputRoot::resolve(settings.input_dir);
+        input_root.register_asset_source(app);
         app.add_plugins((DefaultPlugins, bevy::feathers::FeathersPlugins));
-        app.insert_resource(bevy_pkv::PkvStore::new("Phox", "AoB"));
+        app.add_plugins(CorePlugin {
+            input_root,
+            store: settings.store,
+        });
         app.init_resource::<Seed>();
-        app.add_plugins(days::DaysPlugin);
-        app.init_state::<state::Day>();
-        app.init_resource::<book_keeping::CurrentDayRaw>();
-        app.insert_resource(Time::<Fixed>::from_hz(3.));
         app.add_plugins(ui::UIPlugin);
+        app.add_plugins(toast::ToastPlugin);
+        app.add_plugins(input::InputSelectorPlugin);
+        app.add_plugins(diagnostics::DiagnosticsPlugin);
+        app.add_plugins(onboarding::OnboardingPlugin);
+        app.add_plugins(transfer::TransferPlugin);
+        app.add_plugins(profile::ProfilePlugin);
+        app.add_plugins(reset::ResetPlugin);
+        app.add_plugins(timing::TimingPlugin);
         app.add_plugins(calendar::CalendarPlugin);
         app.add_systems(Startup, spawn_camera);
         app.insert_resource(bevy::feathers::theme::UiTheme(create_dark_theme()));
-        app.init_resource::<book_keeping::Anwsers>();
+    }
+}
+
+/// Everything that works without a window, shared by [`AoCPlugin`] and [`HeadlessPlugin`]
+struct CorePlugin {
+    input_root: InputRoot,
+    store: StoreBackend,
+}
+
+impl Plugin for CorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.input_root.clone());
+        app.add_plugins(input::PuzzleInputPlugin);
+        app.add_plugins(days::DaysPlugin);
+        let mut store = self.store.open();
+        year::migrate_save(store.as_mut());
+        let (saves, problem) = profile::SaveStore::open(store, app.world().resource::<Years>());
+        app.insert_resource(saves);
+        if let Some(problem) = problem {
+            save::report_save_problem(app.world_mut(), problem);
+        }
+        app.init_state::<state::Day>();
+        app.init_resource::<book_keeping::CurrentDayRaw>();
+        app.insert_resource(Time::<Fixed>::from_hz(3.));
+        app.init_resource::<cooldown::CooldownPolicy>();
+        app.init_resource::<unlock::UnlockPolicy>();
+        // loads the year's calendar, history, cooldowns, timings, answers and input folder
+        let year = year::Year::from_world(app.world_mut());
+        year::open_year(app.world_mut(), year);
+        app.add_systems(
+            Update,
+            save::report_save_problems.run_if(resource_exists::<save::SaveProblems>),
+        );
+        app.add_observer(year::select_year);
+        app.add_observer(transfer::export_progress);
+        app.add_observer(transfer::import_progress);
+        app.add_observer(profile::select_profile);
+        app.add_observer(reset::reset_progress);
+        app.add_observer(timing::record_timing);
         app.add_observer(book_keeping::submit_answers);
+        app.add_observer(providers::set_manual_answer);
+        app.add_observer(diagnostics::store_parse_report);
     }
 }
 
@@ -38,10 +156,25 @@ fn spawn_camera(mut commands: Commands) {
 
 #[derive(strum_macros::AsRefStr)]
 pub enum PKVKeys {
+    /// The calendar from before there were years, moved to 2025's [`PKVKeys::CalendarState`]
     CalendarState25,
+    /// Saves for each year are kept under the key with the year on the end, see [`Year::key`],
+    /// and everything is kept apart for each profile, see [`SaveStore`]
+    CalendarState,
+    /// The year that was open last
+    Year,
     Seed,
+    History,
+    ManualAnswers,
+    Cooldowns,
+    CalendarLayout,
+    /// Best solve times of each day
+    Timings,
+    /// Every profile, the only key that isn't kept apart for each one
+    Profiles,
 }
 
 mod calendar;
 
 pub use book_keeping::Seed;
+pub use calendar::{CalendarLayout, Placement, RerollSeed};
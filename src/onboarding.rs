@@ -0,0 +1,285 @@
+use std::path::PathBuf;
+
+use bevy::{
+    feathers::{
+        controls::{ButtonProps, ButtonVariant, button},
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    input_focus::InputFocus,
+    prelude::*,
+};
+
+use crate::{Day, Toast, book_keeping::MissingInput};
+
+/// How many lines of the pasted input are shown
+const PREVIEW_LINES: usize = 12;
+
+/// Asks for a day's input when it's missing or empty instead of opening the day
+pub struct OnboardingPlugin;
+
+impl Plugin for OnboardingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(spawn_onboarding);
+        app.add_systems(
+            Update,
+            (forget_closed, type_input, update_preview)
+                .chain()
+                .run_if(resource_exists::<Onboarding>),
+        );
+    }
+}
+
+/// The input being pasted in, removed when onboarding closes
+#[derive(Resource)]
+struct Onboarding {
+    day: Day,
+    path: PathBuf,
+    text: String,
+}
+
+#[derive(Component)]
+struct OnboardingPanel;
+
+#[derive(Component)]
+struct InputPreview;
+
+#[derive(Component, Clone, Copy)]
+enum OnboardingAction {
+    Paste,
+    Clear,
+    Save,
+    Cancel,
+}
+
+fn spawn_onboarding(
+    missing: On<MissingInput>,
+    old: Query<Entity, With<OnboardingPanel>>,
+    state: Res<State<Day>>,
+    mut commands: Commands,
+) {
+    for panel in &old {
+        commands.entity(panel).despawn();
+    }
+    commands.insert_resource(Onboarding {
+        day: missing.day,
+        path: missing.path.clone(),
+        text: String::new(),
+    });
+    let explain = format!(
+        "Day {} needs its {} input, the file is {}.\n\
+         It should be at {}\n\
+         Paste your puzzle input below with Ctrl+V or the Paste button, or save it to that file yourself.",
+        missing.day.0,
+        missing.name,
+        missing.reason,
+        missing.path.display()
+    );
+    let panel = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(60.),
+                left: Val::Percent(20.),
+                top: Val::Percent(10.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.),
+                padding: UiRect::all(Val::Px(20.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(15.)),
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(5),
+            // a day's other inputs are picked from inside the day
+            DespawnOnExit(*state.get()),
+            OnboardingPanel,
+        ))
+        .with_children(|p| {
+            p.spawn((Text::new(explain), ThemedText));
+            p.spawn((
+                Node {
+                    min_height: Val::Px(200.),
+                    padding: UiRect::all(Val::Px(10.)),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                BorderRadius::all(Val::Px(8.)),
+                BackgroundColor(Color::BLACK.with_alpha(0.4)),
+                children![(
+                    Text::default(),
+                    TextFont::from_font_size(14.),
+                    ThemedText,
+                    InputPreview,
+                )],
+            ));
+            p.spawn(Node {
+                column_gap: Val::Px(8.),
+                ..Default::default()
+            })
+            .with_children(|p| {
+                for (label, action, variant) in [
+                    ("Paste", OnboardingAction::Paste, ButtonVariant::Normal),
+                    ("Clear", OnboardingAction::Clear, ButtonVariant::Normal),
+                    (
+                        "Save and open",
+                        OnboardingAction::Save,
+                        ButtonVariant::Primary,
+                    ),
+                    ("Cancel", OnboardingAction::Cancel, ButtonVariant::Normal),
+                ] {
+                    p.spawn(button(
+                        ButtonProps {
+                            variant,
+                            ..Default::default()
+                        },
+                        action,
+                        Spawn(Text::new(label)),
+                    ))
+                    .observe(on_action);
+                }
+            });
+        })
+        .id();
+    // keeps Enter and Space from activating the calendar tile behind the panel
+    commands.insert_resource(InputFocus(Some(panel)));
+}
+
+fn on_action(
+    event: On<bevy::ui_widgets::Activate>,
+    actions: Query<&OnboardingAction>,
+    mut commands: Commands,
+) {
+    if let Ok(action) = actions.get(event.entity) {
+        commands.queue(run_action(*action));
+    }
+}
+
+fn run_action(action: OnboardingAction) -> impl FnOnce(&mut World) {
+    move |world: &mut World| {
+        let Some(mut onboarding) = world.get_resource_mut::<Onboarding>() else {
+            return;
+        };
+        match action {
+            OnboardingAction::Paste => match paste() {
+                Ok(text) => onboarding.text.push_str(&text),
+                Err(e) => {
+                    world.trigger(Toast::error(format!("Couldn't read the clipboard: {}", e)))
+                }
+            },
+            OnboardingAction::Clear => onboarding.text.clear(),
+            OnboardingAction::Save => save(world),
+            OnboardingAction::Cancel => close(world),
+        }
+    }
+}
+
+fn paste() -> Result<String, arboard::Error> {
+    arboard::Clipboard::new()?.get_text()
+}
+
+/// Writes the input to where the day expects it then opens the day
+fn save(world: &mut World) {
+    let Some(onboarding) = world.get_resource::<Onboarding>() else {
+        return;
+    };
+    if onboarding.text.trim().is_empty() {
+        world.trigger(Toast::error("Paste the puzzle input first"));
+        return;
+    }
+    let day = onboarding.day;
+    let path = onboarding.path.clone();
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, &onboarding.text));
+    if let Err(e) = written {
+        let message = format!("Failed to write {}: {}", path.display(), e);
+        error!("{}", message);
+        world.trigger(Toast::error(message));
+        return;
+    }
+    close(world);
+    world.trigger(Toast::info(format!("Saved day {} input", day.0)));
+    world.trigger(day);
+}
+
+fn close(world: &mut World) {
+    world.remove_resource::<Onboarding>();
+    world.insert_resource(InputFocus(None));
+    let panels = world
+        .query_filtered::<Entity, With<OnboardingPanel>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for panel in panels {
+        world.despawn(panel);
+    }
+}
+
+/// The panel goes with the day it was opened in
+fn forget_closed(panels: Query<(), With<OnboardingPanel>>, mut commands: Commands) {
+    if panels.is_empty() {
+        commands.remove_resource::<Onboarding>();
+    }
+}
+
+fn type_input(
+    mut keys: MessageReader<KeyboardInput>,
+    held: Res<ButtonInput<KeyCode>>,
+    mut onboarding: ResMut<Onboarding>,
+    mut commands: Commands,
+) {
+    let ctrl = held.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Character(c) if ctrl && c.eq_ignore_ascii_case("v") => {
+                commands.queue(run_action(OnboardingAction::Paste));
+            }
+            Key::Character(c) if !ctrl => onboarding.text.push_str(c),
+            Key::Space => onboarding.text.push(' '),
+            Key::Enter => onboarding.text.push('\n'),
+            Key::Backspace => {
+                onboarding.text.pop();
+            }
+            Key::Escape => commands.queue(run_action(OnboardingAction::Cancel)),
+            _ => {}
+        }
+    }
+}
+
+fn update_preview(onboarding: Res<Onboarding>, mut preview: Query<&mut Text, With<InputPreview>>) {
+    if !onboarding.is_changed() {
+        return;
+    }
+    let text = &onboarding.text;
+    let lines = text.lines().count();
+    let mut shown = text
+        .lines()
+        .take(PREVIEW_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.is_empty() {
+        shown = "Nothing pasted yet".to_string();
+    } else if lines > PREVIEW_LINES {
+        shown.push_str(&format!(
+            "\n... {} lines, {} characters",
+            lines,
+            text.chars().count()
+        ));
+    }
+    for mut preview in &mut preview {
+        preview.0.clone_from(&shown);
+    }
+}
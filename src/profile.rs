@@ -0,0 +1,642 @@
+use bevy::{
+    feathers::{
+        controls::{ButtonProps, ButtonVariant, button},
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    input_focus::InputFocus,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    CalendarLayout, Day, PKVKeys, ProgressStore, Seed, StoreError, Toast, Year, Years,
+    year::move_save,
+};
+
+/// Longest name a profile can have
+const MAX_NAME: usize = 24;
+
+/// Someone using the app, each has their own saves
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+/// Every profile and which one was active last, kept under [`PKVKeys::Profiles`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Profiles {
+    list: Vec<Profile>,
+    active: u32,
+    next_id: u32,
+}
+
+/// The [`ProgressStore`] saves go to, with every key kept apart for each [`Profile`]
+#[derive(Resource)]
+pub struct SaveStore {
+    store: Box<dyn ProgressStore>,
+    profiles: Profiles,
+}
+
+impl Profiles {
+    fn first() -> Profiles {
+        Profiles {
+            list: vec![Profile {
+                id: 0,
+                name: "Player 1".to_string(),
+            }],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl SaveStore {
+    /// Saves from before there were profiles go to the first one
+    pub fn new(store: Box<dyn ProgressStore>, years: &Years) -> SaveStore {
+        SaveStore::open(store, years).0
+    }
+
+    /// Like [`SaveStore::new`], with why the profiles couldn't be loaded if they couldn't.
+    /// Unreadable profiles are kept under `ProfilesUnreadable` and the first profile's saves are used
+    pub(crate) fn open(
+        mut store: Box<dyn ProgressStore>,
+        years: &Years,
+    ) -> (SaveStore, Option<String>) {
+        let key = PKVKeys::Profiles.as_ref();
+        let (profiles, problem) = match store.get(key).and_then(from_value) {
+            Ok(profiles) => (profiles, None),
+            Err(StoreError::NotFound) => {
+                let profiles = Profiles::first();
+                migrate_save(store.as_mut(), &profiles.list[0], years);
+                (profiles, None)
+            }
+            Err(e) => {
+                let backup = format!("{}Unreadable", key);
+                let mut message = format!("Couldn't load the profiles, {}", e);
+                match store.get(key).and_then(|save| store.set(&backup, save)) {
+                    Ok(()) => message.push_str(&format!("; they were kept as {}", backup)),
+                    // writing over profiles that couldn't be kept would lose them
+                    Err(_) => {
+                        error!("{}", message);
+                        let saves = SaveStore {
+                            store,
+                            profiles: Profiles::first(),
+                        };
+                        return (saves, Some(message));
+                    }
+                }
+                error!("{}", message);
+                (Profiles::first(), Some(message))
+            }
+        };
+        let mut saves = SaveStore { store, profiles };
+        saves.save_profiles();
+        (saves, problem)
+    }
+
+    /// The key `key` is kept under for a profile, e.g. `Profile0/History2025`
+    pub fn profile_key(profile: u32, key: impl AsRef<str>) -> String {
+        format!("Profile{}/{}", profile, key.as_ref())
+    }
+
+    fn key(&self, key: impl AsRef<str>) -> String {
+        SaveStore::profile_key(self.profiles.active, key)
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: impl AsRef<str>) -> Result<T, StoreError> {
+        from_value(self.store.get(&self.key(key))?)
+    }
+
+    pub fn set<T: Serialize>(&mut self, key: impl AsRef<str>, value: &T) -> Result<(), StoreError> {
+        let value = serde_json::to_value(value).map_err(|e| StoreError::Format(e.to_string()))?;
+        let key = self.key(key);
+        self.store.set(&key, value)
+    }
+
+    pub fn remove(&mut self, key: impl AsRef<str>) -> Result<(), StoreError> {
+        let key = self.key(key);
+        self.store.remove(&key)
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles.list
+    }
+
+    pub fn active(&self) -> &Profile {
+        self.profile(self.profiles.active)
+            .unwrap_or(&self.profiles.list[0])
+    }
+
+    pub fn profile(&self, id: u32) -> Option<&Profile> {
+        self.profiles.list.iter().find(|profile| profile.id == id)
+    }
+
+    /// Adds a profile, it isn't made active
+    pub fn create_profile(&mut self, name: &str) -> Result<u32, String> {
+        let name = self.check_name(name, None)?;
+        let id = self.profiles.next_id;
+        self.profiles.next_id += 1;
+        self.profiles.list.push(Profile { id, name });
+        self.save_profiles();
+        Ok(id)
+    }
+
+    pub fn rename_profile(&mut self, id: u32, name: &str) -> Result<(), String> {
+        let name = self.check_name(name, Some(id))?;
+        let profile = self
+            .profiles
+            .list
+            .iter_mut()
+            .find(|profile| profile.id == id)
+            .ok_or("There is no such profile")?;
+        profile.name = name;
+        self.save_profiles();
+        Ok(())
+    }
+
+    /// Removes a profile and all its saves, the first profile left is made active if it was
+    pub fn delete_profile(&mut self, id: u32, years: &Years) -> Result<(), String> {
+        if self.profiles.list.len() == 1 {
+            return Err("The last profile can't be deleted".to_string());
+        }
+        let Some(index) = self.profiles.list.iter().position(|p| p.id == id) else {
+            return Err("There is no such profile".to_string());
+        };
+        self.profiles.list.remove(index);
+        if let Err(e) = self.store.remove_prefix(&SaveStore::profile_key(id, "")) {
+            error!("Failed to remove the saves of profile {}: {}", id, e);
+        }
+        // pkv only lists the keys saved since it started keeping an index
+        for key in [PKVKeys::Seed, PKVKeys::CalendarLayout, PKVKeys::Year] {
+            _ = self.store.remove(&SaveStore::profile_key(id, key));
+        }
+        for year in years.0.values() {
+            for key in YEAR_KEYS {
+                _ = self
+                    .store
+                    .remove(&SaveStore::profile_key(id, year.key(key)));
+            }
+        }
+        if self.profiles.active == id {
+            self.profiles.active = self.profiles.list[0].id;
+        }
+        self.save_profiles();
+        Ok(())
+    }
+
+    /// Makes a profile active, [`SelectProfile`] also loads what it has saved
+    pub fn set_active(&mut self, id: u32) -> Result<(), String> {
+        if self.profile(id).is_none() {
+            return Err("There is no such profile".to_string());
+        }
+        self.profiles.active = id;
+        self.save_profiles();
+        Ok(())
+    }
+
+    fn check_name(&self, name: &str, renaming: Option<u32>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profiles need a name".to_string());
+        }
+        if name.chars().count() > MAX_NAME {
+            return Err(format!("Names can be up to {} characters", MAX_NAME));
+        }
+        let taken = self
+            .profiles
+            .list
+            .iter()
+            .any(|profile| profile.name == name && Some(profile.id) != renaming);
+        if taken {
+            return Err(format!("There is already a profile called {}", name));
+        }
+        Ok(name.to_string())
+    }
+
+    fn save_profiles(&mut self) {
+        let profiles =
+            serde_json::to_value(&self.profiles).map_err(|e| StoreError::Format(e.to_string()));
+        if let Err(e) =
+            profiles.and_then(|profiles| self.store.set(PKVKeys::Profiles.as_ref(), profiles))
+        {
+            error!("Failed to save profiles: {}", e);
+        }
+    }
+}
+
+fn from_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, StoreError> {
+    serde_json::from_value(value).map_err(|e| StoreError::Format(e.to_string()))
+}
+
+/// The keys each year's saves are kept under, see [`Year::key`]
+const YEAR_KEYS: [PKVKeys; 5] = [
+    PKVKeys::CalendarState,
+    PKVKeys::History,
+    PKVKeys::ManualAnswers,
+    PKVKeys::Cooldowns,
+    PKVKeys::Timings,
+];
+
+/// Moves the saves from before there were profiles to `profile`
+fn migrate_save(store: &mut dyn ProgressStore, profile: &Profile, years: &Years) {
+    let to = |key: &str| SaveStore::profile_key(profile.id, key);
+    for key in [PKVKeys::Seed, PKVKeys::CalendarLayout, PKVKeys::Year] {
+        move_save(store, &key, to(key.as_ref()));
+    }
+    for year in years.0.values() {
+        for key in YEAR_KEYS {
+            let key = year.key(key);
+            move_save(store, &key, to(&key));
+        }
+    }
+}
+
+/// Loads everything that is read from the [`SaveStore`] again, after a profile change or an import
+pub(crate) fn reload_saves(world: &mut World) {
+    if world.contains_resource::<Seed>() {
+        let seed = Seed::from_world(world);
+        world.insert_resource(seed);
+    }
+    if world.contains_resource::<CalendarLayout>() {
+        let layout = CalendarLayout::from_world(world);
+        world.insert_resource(layout);
+    }
+    let year = Year::from_world(world);
+    crate::year::open_year(world, year);
+    world.trigger(year);
+}
+
+/// Trigger to switch to another profile
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SelectProfile(pub u32);
+
+pub(crate) fn select_profile(select: On<SelectProfile>, mut commands: Commands) {
+    let id = select.0;
+    commands.queue(move |world: &mut World| {
+        match world.resource_mut::<SaveStore>().set_active(id) {
+            Ok(()) => reload_saves(world),
+            Err(e) => {
+                error!("{}", e);
+                world.trigger(Toast::error(e));
+            }
+        }
+    });
+}
+
+/// Picks who is playing, shown before the calendar when the app starts
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(Day(0)),
+            (open_profile_screen.run_if(run_once), spawn_profile_button),
+        );
+        app.add_observer(on_profile_action);
+        app.add_systems(
+            Update,
+            (type_profile_name, rebuild_profile_screen)
+                .chain()
+                .run_if(resource_exists::<ProfileScreen>),
+        );
+        app.add_systems(
+            Update,
+            update_profile_label.run_if(resource_changed::<SaveStore>),
+        );
+    }
+}
+
+/// The profile screen is open, removed when it closes
+#[derive(Resource, Default)]
+struct ProfileScreen {
+    /// The name being typed, for a new profile when the id is `None`
+    editing: Option<(Option<u32>, String)>,
+    /// Profile to delete when Delete is pressed again
+    deleting: Option<u32>,
+}
+
+#[derive(Component)]
+struct ProfilePanel;
+
+#[derive(Component)]
+struct ProfileLabel;
+
+#[derive(Component, Clone, Copy)]
+enum ProfileAction {
+    Open,
+    Select(u32),
+    Rename(u32),
+    Delete(u32),
+    New,
+    Close,
+}
+
+fn open_profile_screen(mut commands: Commands) {
+    commands.init_resource::<ProfileScreen>();
+}
+
+fn spawn_profile_button(mut commands: Commands, saves: Res<SaveStore>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..Default::default()
+        },
+        DespawnOnExit(Day(0)),
+        GlobalZIndex(1),
+        children![button(
+            ButtonProps::default(),
+            ProfileAction::Open,
+            Spawn((
+                Text::new(format!("Profile: {}", saves.active().name)),
+                ProfileLabel,
+            )),
+        )],
+    ));
+}
+
+fn rebuild_profile_screen(
+    screen: Res<ProfileScreen>,
+    saves: Res<SaveStore>,
+    old: Query<Entity, With<ProfilePanel>>,
+    mut commands: Commands,
+) {
+    if !screen.is_changed() && !saves.is_changed() {
+        return;
+    }
+    for panel in &old {
+        commands.entity(panel).despawn();
+    }
+    let active = saves.active().id;
+    let panel = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.),
+                ..Default::default()
+            },
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(6),
+            DespawnOnExit(Day(0)),
+            ProfilePanel,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Who's playing?"),
+                TextFont::from_font_size(24.),
+                ThemedText,
+            ));
+            for profile in saves.profiles() {
+                p.spawn(Node {
+                    column_gap: Val::Px(8.),
+                    ..Default::default()
+                })
+                .with_children(|p| {
+                    let variant = if profile.id == active {
+                        ButtonVariant::Primary
+                    } else {
+                        ButtonVariant::Normal
+                    };
+                    let delete = if screen.deleting == Some(profile.id) {
+                        "Delete for good?"
+                    } else {
+                        "Delete"
+                    };
+                    for (label, action, variant) in [
+                        (
+                            profile.name.as_str(),
+                            ProfileAction::Select(profile.id),
+                            variant,
+                        ),
+                        (
+                            "Rename",
+                            ProfileAction::Rename(profile.id),
+                            ButtonVariant::Normal,
+                        ),
+                        (
+                            delete,
+                            ProfileAction::Delete(profile.id),
+                            ButtonVariant::Normal,
+                        ),
+                    ] {
+                        p.spawn(button(
+                            ButtonProps {
+                                variant,
+                                ..Default::default()
+                            },
+                            action,
+                            Spawn(Text::new(label)),
+                        ));
+                    }
+                });
+            }
+            match &screen.editing {
+                Some((renaming, name)) => {
+                    let what = match renaming.and_then(|id| saves.profile(id)) {
+                        Some(profile) => format!("Rename {} to", profile.name),
+                        None => "New profile called".to_string(),
+                    };
+                    p.spawn((Text::new(format!("{}: {}_", what, name)), ThemedText));
+                    p.spawn((
+                        Text::new("Enter to save, Escape to cancel"),
+                        TextFont::from_font_size(14.),
+                        ThemedText,
+                    ));
+                }
+                None => {
+                    p.spawn(Node {
+                        column_gap: Val::Px(8.),
+                        ..Default::default()
+                    })
+                    .with_children(|p| {
+                        for (label, action) in [
+                            ("New profile", ProfileAction::New),
+                            ("Close", ProfileAction::Close),
+                        ] {
+                            p.spawn(button(
+                                ButtonProps::default(),
+                                action,
+                                Spawn(Text::new(label)),
+                            ));
+                        }
+                    });
+                }
+            }
+        })
+        .id();
+    // keeps Enter and Space from activating the calendar tile behind the screen
+    commands.insert_resource(InputFocus(Some(panel)));
+}
+
+fn on_profile_action(
+    event: On<bevy::ui_widgets::Activate>,
+    actions: Query<&ProfileAction>,
+    mut commands: Commands,
+) {
+    let Ok(action) = actions.get(event.entity) else {
+        return;
+    };
+    let action = *action;
+    commands.queue(move |world: &mut World| run_action(world, action));
+}
+
+fn run_action(world: &mut World, action: ProfileAction) {
+    if let ProfileAction::Open = action {
+        world.init_resource::<ProfileScreen>();
+        return;
+    }
+    let Some(mut screen) = world.get_resource_mut::<ProfileScreen>() else {
+        return;
+    };
+    // Delete has to be pressed twice in a row
+    let deleting = screen.deleting.take();
+    match action {
+        ProfileAction::Open => {}
+        ProfileAction::Select(id) => {
+            close(world);
+            world.trigger(SelectProfile(id));
+        }
+        ProfileAction::Rename(id) => {
+            let name = world
+                .resource::<SaveStore>()
+                .profile(id)
+                .map(|profile| profile.name.clone())
+                .unwrap_or_default();
+            world.resource_mut::<ProfileScreen>().editing = Some((Some(id), name));
+        }
+        ProfileAction::Delete(id) if deleting != Some(id) => screen.deleting = Some(id),
+        ProfileAction::Delete(id) => delete(world, id),
+        ProfileAction::New => screen.editing = Some((None, String::new())),
+        ProfileAction::Close => close(world),
+    }
+}
+
+fn delete(world: &mut World, id: u32) {
+    let result = world.resource_scope(|world, mut saves: Mut<SaveStore>| {
+        let active = saves.active().id;
+        saves
+            .delete_profile(id, world.resource::<Years>())
+            .map(|()| active == id)
+    });
+    match result {
+        Ok(was_active) => {
+            if was_active {
+                reload_saves(world);
+            }
+            world.trigger(Toast::info("Deleted the profile"));
+        }
+        Err(e) => world.trigger(Toast::error(e)),
+    }
+}
+
+fn close(world: &mut World) {
+    world.remove_resource::<ProfileScreen>();
+    world.insert_resource(InputFocus(None));
+    let panels = world
+        .query_filtered::<Entity, With<ProfilePanel>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for panel in panels {
+        world.despawn(panel);
+    }
+}
+
+fn type_profile_name(
+    mut keys: MessageReader<KeyboardInput>,
+    mut screen: ResMut<ProfileScreen>,
+    mut commands: Commands,
+) {
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+        let Some((renaming, name)) = &mut screen.editing else {
+            if key.logical_key == Key::Escape {
+                commands.queue(close);
+            }
+            continue;
+        };
+        match &key.logical_key {
+            Key::Character(c) => name.push_str(c),
+            Key::Space => name.push(' '),
+            Key::Backspace => {
+                name.pop();
+            }
+            Key::Enter => {
+                let (renaming, name) = (*renaming, name.clone());
+                commands.queue(move |world: &mut World| save_name(world, renaming, &name));
+            }
+            Key::Escape => screen.editing = None,
+            _ => {}
+        }
+    }
+}
+
+fn save_name(world: &mut World, renaming: Option<u32>, name: &str) {
+    let mut saves = world.resource_mut::<SaveStore>();
+    let result = match renaming {
+        Some(id) => saves.rename_profile(id, name),
+        None => saves.create_profile(name).map(|_| ()),
+    };
+    match result {
+        Ok(()) => world.resource_mut::<ProfileScreen>().editing = None,
+        Err(e) => world.trigger(Toast::error(e)),
+    }
+}
+
+fn update_profile_label(saves: Res<SaveStore>, mut labels: Query<&mut Text, With<ProfileLabel>>) {
+    for mut label in &mut labels {
+        label.0 = format!("Profile: {}", saves.active().name);
+    }
+}
+
+#[test]
+fn profiles_keep_saves_apart() {
+    let mut store = crate::MemoryStore::default();
+    store.set(PKVKeys::Seed.as_ref(), 7.into()).unwrap();
+    let mut saves = SaveStore::new(Box::new(store), &Years::default());
+    assert_eq!(saves.get::<u64>(PKVKeys::Seed).unwrap(), 7);
+    let mut broken = crate::MemoryStore::default();
+    broken
+        .set(PKVKeys::Profiles.as_ref(), "Phox".into())
+        .unwrap();
+    let (broken, problem) = SaveStore::open(Box::new(broken), &Years::default());
+    assert!(problem.is_some());
+    assert_eq!(broken.store.get("ProfilesUnreadable").unwrap(), "Phox");
+    assert_eq!(broken.active().id, 0);
+    let second = saves.create_profile("Phox").unwrap();
+    assert!(saves.create_profile(" Phox ").is_err());
+    saves.set_active(second).unwrap();
+    assert!(saves.get::<u64>(PKVKeys::Seed).is_err());
+    saves.set(PKVKeys::Seed, &8u64).unwrap();
+    saves.rename_profile(second, "Fox").unwrap();
+    assert_eq!(saves.active().name, "Fox");
+    // not one of the keys the profile is known to save
+    saves.set("History1999Unreadable", &1u8).unwrap();
+    saves.delete_profile(second, &Years::default()).unwrap();
+    let prefix = SaveStore::profile_key(second, "");
+    assert!(
+        saves
+            .store
+            .keys()
+            .unwrap()
+            .iter()
+            .all(|key| !key.starts_with(&prefix))
+    );
+    assert_eq!(saves.active().id, 0);
+    assert_eq!(saves.get::<u64>(PKVKeys::Seed).unwrap(), 7);
+    assert!(saves.delete_profile(0, &Years::default()).is_err());
+}
@@ -0,0 +1,477 @@
+use std::{any::Any, collections::BTreeMap, path::Path};
+
+use bevy::{asset::ron, platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AnswerValue, PKVKeys, Puzzle, Results, SaveStore, Toast, Year, book_keeping::Anwsers,
+    save::Versioned,
+};
+
+/// Somewhere correct answers can come from.
+///
+/// Providers are registered on [`Anwsers`] and asked in order,
+/// the first one that doesn't return [`Results::Missing`] decides the result.
+pub trait AnswerProvider: Any + Send + Sync {
+    /// Shown on the calendar next to the stars this provider confirmed
+    fn name(&self) -> &str;
+    fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results;
+}
+
+/// Answers from the built-in `solve_part1`/`solve_part2` observers
+#[derive(Default)]
+pub struct ReferenceSolvers {
+    answers: HashMap<(u8, Puzzle), AnswerValue>,
+}
+
+impl ReferenceSolvers {
+    pub fn add(&mut self, day: u8, puzzle: Puzzle, answer: AnswerValue) {
+        self.answers.insert((day, puzzle), answer);
+    }
+
+    pub fn get(&self, day: u8, puzzle: Puzzle) -> Option<&AnswerValue> {
+        self.answers.get(&(day, puzzle))
+    }
+}
+
+impl AnswerProvider for ReferenceSolvers {
+    fn name(&self) -> &str {
+        "reference"
+    }
+
+    fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results {
+        match self.get(day, puzzle) {
+            Some(correct) => Results::compare(correct, answer),
+            None => Results::Missing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyEntry<T = AnswerValue> {
+    pub part1: Option<T>,
+    pub part2: Option<T>,
+}
+
+impl<T> Default for KeyEntry<T> {
+    fn default() -> Self {
+        KeyEntry {
+            part1: None,
+            part2: None,
+        }
+    }
+}
+
+impl<T> KeyEntry<T> {
+    pub fn get(&self, puzzle: Puzzle) -> Option<&T> {
+        match puzzle {
+            Puzzle::Part1 => self.part1.as_ref(),
+            Puzzle::Part2 => self.part2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, puzzle: Puzzle, answer: T) {
+        match puzzle {
+            Puzzle::Part1 => self.part1 = Some(answer),
+            Puzzle::Part2 => self.part2 = Some(answer),
+        }
+    }
+}
+
+/// `Some(..)` is optional around answers so files stay easy to write by hand
+pub(crate) fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+}
+
+/// Answers read from a RON file keyed by day, for example:
+/// ```ron
+/// {
+///     1: (part1: 1034, part2: "6166"),
+///     2: (part1: 18952700150),
+/// }
+/// ```
+#[derive(Default)]
+pub struct AnswerKey {
+    days: BTreeMap<u8, KeyEntry>,
+}
+
+impl AnswerKey {
+    pub const PATH: &str = "assets/answers.ron";
+
+    pub fn load(path: impl AsRef<Path>) -> Result<AnswerKey, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        AnswerKey::parse(&text).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<AnswerKey, ron::error::SpannedError> {
+        Ok(AnswerKey {
+            days: ron_options().from_str(text)?,
+        })
+    }
+
+    /// Every answer in the key, used to build a [`HashedAnswerKey`]
+    pub fn answers(&self) -> impl Iterator<Item = (u8, Puzzle, &AnswerValue)> {
+        self.days.iter().flat_map(|(day, entry)| {
+            [Puzzle::Part1, Puzzle::Part2]
+                .into_iter()
+                .filter_map(move |puzzle| entry.get(puzzle).map(|answer| (*day, puzzle, answer)))
+        })
+    }
+}
+
+impl AnswerProvider for AnswerKey {
+    fn name(&self) -> &str {
+        "answer key"
+    }
+
+    fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results {
+        match self.days.get(&day).and_then(|entry| entry.get(puzzle)) {
+            Some(correct) => Results::compare(correct, answer),
+            None => Results::Missing,
+        }
+    }
+}
+
+/// A salted hash of an answer, optionally with an encrypted range the answer is in
+/// so guesses outside it still get a higher/lower hint.
+///
+/// This keeps answers out of plain sight, it doesn't make them secret:
+/// a salted SHA-256 of a small number is found by hashing every number up to it,
+/// and the range is encrypted with a key built into the game, so anyone with the source can read it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashedAnswer {
+    pub salt: String,
+    pub hash: String,
+    #[serde(default)]
+    pub bounds: Option<String>,
+}
+
+impl HashedAnswer {
+    /// `window` is how far either side of the answer the hint bounds sit, as a percentage.
+    /// Text answers never get bounds
+    pub fn new(day: u8, puzzle: Puzzle, answer: &AnswerValue, window: Option<u8>) -> HashedAnswer {
+        let salt = to_hex(&rand::random::<[u8; 16]>());
+        let hash = hash_answer(&salt, day, puzzle, answer);
+        let bounds = window
+            .and_then(|window| answer_bounds(answer, window))
+            .map(|bounds| encrypt_bounds(&salt, day, puzzle, bounds));
+        HashedAnswer { salt, hash, bounds }
+    }
+
+    pub fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results {
+        if hash_answer(&self.salt, day, puzzle, answer) == self.hash {
+            return Results::Correct;
+        }
+        let Some((lower, upper)) = self
+            .bounds
+            .as_ref()
+            .and_then(|bounds| decrypt_bounds(&self.salt, day, puzzle, bounds))
+        else {
+            return Results::Incorrect;
+        };
+        if answer.cmp_numeric(&lower.into()) == Some(std::cmp::Ordering::Less) {
+            Results::Higher
+        } else if answer.cmp_numeric(&upper.into()) == Some(std::cmp::Ordering::Greater) {
+            Results::Lower
+        } else {
+            Results::Incorrect
+        }
+    }
+}
+
+fn hash_answer(salt: &str, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> String {
+    use sha2::Digest;
+    let hash = sha2::Sha256::new()
+        .chain_update(salt)
+        .chain_update([day, puzzle as u8])
+        .chain_update(answer.to_string())
+        .finalize();
+    to_hex(&hash)
+}
+
+fn answer_bounds(answer: &AnswerValue, window: u8) -> Option<(i128, i128)> {
+    let answer = match answer {
+        AnswerValue::Signed(value) => *value,
+        AnswerValue::Unsigned(value) => i128::try_from(*value).ok()?,
+        AnswerValue::Text(_) => return None,
+    };
+    let offset = (answer.unsigned_abs() / 100 * window as u128).max(1) as i128;
+    Some((answer.saturating_sub(offset), answer.saturating_add(offset)))
+}
+
+/// Mixed into the key the hint bounds are encrypted with, so the key file alone can't decrypt them
+const BOUNDS_KEY: &[u8] = b"advent_of_bevy hashed answer bounds";
+
+/// The ChaCha20 keystream for the bounds of one answer, and the key their tag is made with
+fn bounds_cipher(salt: &str, day: u8, puzzle: Puzzle) -> (rand_chacha::ChaCha20Rng, [u8; 32]) {
+    use rand::SeedableRng;
+    use sha2::Digest;
+    let key = sha2::Sha256::new()
+        .chain_update(BOUNDS_KEY)
+        .chain_update(salt)
+        .chain_update([day, puzzle as u8])
+        .finalize()
+        .into();
+    let tag_key = sha2::Sha256::new()
+        .chain_update(key)
+        .chain_update("tag")
+        .finalize()
+        .into();
+    (rand_chacha::ChaCha20Rng::from_seed(key), tag_key)
+}
+
+fn bounds_tag(tag_key: &[u8; 32], ciphertext: &[u8]) -> [u8; 8] {
+    use sha2::Digest;
+    let tag = sha2::Sha256::new()
+        .chain_update(tag_key)
+        .chain_update(ciphertext)
+        .finalize();
+    tag[..8].try_into().expect("SHA-256 is longer than the tag")
+}
+
+fn encrypt_bounds(salt: &str, day: u8, puzzle: Puzzle, (lower, upper): (i128, i128)) -> String {
+    use rand::RngCore;
+    let (mut cipher, tag_key) = bounds_cipher(salt, day, puzzle);
+    let mut bytes = [lower.to_le_bytes(), upper.to_le_bytes()].concat();
+    let mut stream = vec![0; bytes.len()];
+    cipher.fill_bytes(&mut stream);
+    bytes
+        .iter_mut()
+        .zip(stream)
+        .for_each(|(byte, key)| *byte ^= key);
+    let tag = bounds_tag(&tag_key, &bytes);
+    to_hex(&[bytes.as_slice(), &tag].concat())
+}
+
+/// `None` if the bounds were changed or belong to another answer
+fn decrypt_bounds(salt: &str, day: u8, puzzle: Puzzle, bounds: &str) -> Option<(i128, i128)> {
+    use rand::RngCore;
+    let bytes = from_hex(bounds)?;
+    let (ciphertext, tag) = bytes.split_at_checked(32)?;
+    let (mut cipher, tag_key) = bounds_cipher(salt, day, puzzle);
+    if bounds_tag(&tag_key, ciphertext) != tag {
+        return None;
+    }
+    let mut stream = [0; 32];
+    cipher.fill_bytes(&mut stream);
+    let plain = ciphertext
+        .iter()
+        .zip(stream)
+        .map(|(byte, key)| byte ^ key)
+        .collect::<Vec<_>>();
+    let lower = i128::from_le_bytes(plain[..16].try_into().ok()?);
+    let upper = i128::from_le_bytes(plain[16..].try_into().ok()?);
+    Some((lower, upper))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Like [`AnswerKey`] but only holds [`HashedAnswer`]s, so it can be shared without spoiling anything.
+/// Make one from a plaintext key with `cargo run --bin hash_answers`
+#[derive(Default, Serialize, Deserialize)]
+pub struct HashedAnswerKey {
+    days: BTreeMap<u8, KeyEntry<HashedAnswer>>,
+}
+
+impl HashedAnswerKey {
+    pub const PATH: &str = "assets/answers.hashed.ron";
+
+    pub fn load(path: impl AsRef<Path>) -> Result<HashedAnswerKey, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        ron_options()
+            .from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// `window` is passed to [`HashedAnswer::new`], `None` leaves out the hint bounds
+    pub fn from_key(key: &AnswerKey, window: Option<u8>) -> HashedAnswerKey {
+        let mut hashed = HashedAnswerKey::default();
+        for (day, puzzle, answer) in key.answers() {
+            hashed
+                .days
+                .entry(day)
+                .or_default()
+                .set(puzzle, HashedAnswer::new(day, puzzle, answer, window));
+        }
+        hashed
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+impl AnswerProvider for HashedAnswerKey {
+    fn name(&self) -> &str {
+        "hashed key"
+    }
+
+    fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results {
+        match self.days.get(&day).and_then(|entry| entry.get(puzzle)) {
+            Some(hashed) => hashed.check(day, puzzle, answer),
+            None => Results::Missing,
+        }
+    }
+}
+
+/// Answers typed in by hand for the open [`Year`], kept under [`PKVKeys::ManualAnswers`]
+#[derive(Default, Serialize, Deserialize)]
+pub struct ManualAnswers {
+    days: BTreeMap<u8, KeyEntry>,
+}
+
+impl Versioned for ManualAnswers {
+    const VERSION: u32 = 1;
+}
+
+impl ManualAnswers {
+    pub fn get(&self, day: u8, puzzle: Puzzle) -> Option<&AnswerValue> {
+        self.days.get(&day).and_then(|entry| entry.get(puzzle))
+    }
+
+    /// Adds the answers from `other` for parts that don't have one here
+    pub fn merge(&mut self, other: ManualAnswers) {
+        for (day, theirs) in other.days {
+            let ours = self.days.entry(day).or_default();
+            ours.part1 = ours.part1.take().or(theirs.part1);
+            ours.part2 = ours.part2.take().or(theirs.part2);
+        }
+    }
+}
+
+impl AnswerProvider for ManualAnswers {
+    fn name(&self) -> &str {
+        "manual"
+    }
+
+    fn check(&self, day: u8, puzzle: Puzzle, answer: &AnswerValue) -> Results {
+        match self.days.get(&day).and_then(|entry| entry.get(puzzle)) {
+            Some(correct) => Results::compare(correct, answer),
+            None => Results::Missing,
+        }
+    }
+}
+
+/// Trigger to store the correct answer for a day by hand
+#[derive(Event, Debug, Clone)]
+pub struct ManualAnswer {
+    pub day: u8,
+    pub puzzle: Puzzle,
+    pub answer: AnswerValue,
+}
+
+pub(crate) fn set_manual_answer(
+    manual: On<ManualAnswer>,
+    (mut answers, year): (ResMut<Anwsers>, Res<Year>),
+    mut pkv: ResMut<SaveStore>,
+    mut commands: Commands,
+) {
+    let Some(provider) = answers.provider_mut::<ManualAnswers>() else {
+        error!("Manual answers are not registered");
+        return;
+    };
+    provider
+        .days
+        .entry(manual.day)
+        .or_default()
+        .set(manual.puzzle, manual.answer.clone());
+    _ = pkv.store(year.key(PKVKeys::ManualAnswers), &*provider);
+    commands.trigger(Toast::info(format!(
+        "Stored a manual answer for day {} {}",
+        manual.day, manual.puzzle
+    )));
+}
+
+#[test]
+fn answer_key_checks() {
+    let key = AnswerKey::parse("{ 1: (part1: 1034, part2: \"-6\"), 3: (part2: \"abc\") }").unwrap();
+    assert_eq!(
+        key.check(1, Puzzle::Part1, &1034u32.into()),
+        Results::Correct
+    );
+    assert_eq!(key.check(1, Puzzle::Part1, &2000u32.into()), Results::Lower);
+    assert_eq!(
+        key.check(1, Puzzle::Part2, &(-6i32).into()),
+        Results::Correct
+    );
+    assert_eq!(key.check(3, Puzzle::Part1, &1u8.into()), Results::Missing);
+    assert_eq!(
+        key.check(3, Puzzle::Part2, &"abd".into()),
+        Results::Incorrect
+    );
+}
+
+#[test]
+fn hashed_answers_check() {
+    let key = AnswerKey::parse("{ 1: (part1: 1000, part2: \"abc\") }").unwrap();
+    let hashed = HashedAnswerKey::from_key(&key, Some(10));
+    let text = hashed.to_ron().unwrap();
+    // random hex can contain anything, so only look outside the hex strings
+    let unquoted = text.split('"').step_by(2).collect::<String>();
+    assert!(!unquoted.contains("1000") && !text.contains("\"abc\""));
+    assert_eq!(
+        hashed.check(1, Puzzle::Part1, &"1000".into()),
+        Results::Correct
+    );
+    assert_eq!(
+        hashed.check(1, Puzzle::Part1, &850u32.into()),
+        Results::Higher
+    );
+    assert_eq!(
+        hashed.check(1, Puzzle::Part1, &1150u32.into()),
+        Results::Lower
+    );
+    assert_eq!(
+        hashed.check(1, Puzzle::Part1, &1050u32.into()),
+        Results::Incorrect
+    );
+    // bounds copied from another answer don't decrypt
+    let mut moved = hashed.days[&1].part1.clone().unwrap();
+    assert_eq!(
+        moved.check(2, Puzzle::Part1, &850u32.into()),
+        Results::Incorrect
+    );
+    moved.bounds = None;
+    assert_eq!(
+        moved.check(1, Puzzle::Part1, &850u32.into()),
+        Results::Incorrect
+    );
+    let bare = HashedAnswerKey::from_key(&key, None);
+    assert!(bare.days[&1].part1.as_ref().unwrap().bounds.is_none());
+    for guess in [1u32, 850, 1050, 5000] {
+        assert_eq!(
+            bare.check(1, Puzzle::Part1, &guess.into()),
+            Results::Incorrect
+        );
+    }
+    assert_eq!(
+        bare.check(1, Puzzle::Part1, &1000u32.into()),
+        Results::Correct
+    );
+    assert_eq!(
+        hashed.check(1, Puzzle::Part2, &"abc".into()),
+        Results::Correct
+    );
+    assert_eq!(
+        hashed.check(1, Puzzle::Part2, &"abd".into()),
+        Results::Incorrect
+    );
+    assert_eq!(
+        hashed.check(2, Puzzle::Part1, &1u8.into()),
+        Results::Missing
+    );
+}
@@ -0,0 +1,388 @@
+use bevy::{
+    feathers::{
+        controls::{ButtonProps, ButtonVariant, button},
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    input_focus::{InputFocus, InputFocusVisible},
+    picking::hover::Hovered,
+    prelude::*,
+};
+
+use crate::{
+    Cooldowns, Day, PKVKeys, Puzzle, SaveStore, SubmissionHistory, Timings, Toast, Year,
+    calendar::{CalendarState, CalendarTile},
+};
+
+/// Trigger to take away stars and forget the attempts at them in the open [`Year`]
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetProgress {
+    /// Every day when `None`
+    pub day: Option<u8>,
+    /// Both parts when `None`
+    pub puzzle: Option<Puzzle>,
+}
+
+impl ResetProgress {
+    pub fn all() -> ResetProgress {
+        ResetProgress {
+            day: None,
+            puzzle: None,
+        }
+    }
+
+    pub fn day(day: u8) -> ResetProgress {
+        ResetProgress {
+            day: Some(day),
+            puzzle: None,
+        }
+    }
+
+    pub fn part(day: u8, puzzle: Puzzle) -> ResetProgress {
+        ResetProgress {
+            day: Some(day),
+            puzzle: Some(puzzle),
+        }
+    }
+
+    fn describe(&self, year: &Year) -> String {
+        match (self.day, self.puzzle) {
+            (None, _) => format!("every day of {}", year.year),
+            (Some(day), None) => format!("day {}", day),
+            (Some(day), Some(puzzle)) => format!("day {} {}", day, puzzle),
+        }
+    }
+}
+
+pub(crate) fn reset_progress(
+    reset: On<ResetProgress>,
+    year: Res<Year>,
+    mut calendar: ResMut<CalendarState>,
+    (mut history, mut cooldowns): (ResMut<SubmissionHistory>, ResMut<Cooldowns>),
+    mut timings: ResMut<Timings>,
+    mut saves: ResMut<SaveStore>,
+    mut commands: Commands,
+) {
+    if let Some(day) = reset.day.filter(|day| !year.contains(*day)) {
+        let message = format!("Day {} is not part of the {} calendar", day, year.year);
+        error!("{}", message);
+        commands.trigger(Toast::error(message));
+        return;
+    }
+    let days = reset.day.map_or(year.days(), |day| day..=day);
+    let puzzles = match reset.puzzle {
+        Some(puzzle) => vec![puzzle],
+        None => vec![Puzzle::Part1, Puzzle::Part2],
+    };
+    for day in days {
+        for &puzzle in &puzzles {
+            calendar.reset(day, puzzle);
+            history.clear(day, puzzle);
+            cooldowns.clear(day, puzzle);
+        }
+        timings.clear(day, reset.puzzle);
+    }
+    _ = saves.store(year.key(PKVKeys::CalendarState), &*calendar);
+    _ = saves.store(year.key(PKVKeys::History), &*history);
+    _ = saves.store(year.key(PKVKeys::Cooldowns), &*cooldowns);
+    _ = saves.store(year.key(PKVKeys::Timings), &*timings);
+    let message = format!("Reset {}", reset.describe(&year));
+    info!("{}", message);
+    commands.trigger(Toast::info(message));
+}
+
+/// The Reset button on the calendar, and the dialog that confirms a reset
+pub struct ResetPlugin;
+
+impl Plugin for ResetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(Day(0)), spawn_reset_controls);
+        app.add_systems(OnExit(Day(0)), |mut commands: Commands| {
+            commands.remove_resource::<ResetMode>();
+        });
+        app.add_systems(
+            Update,
+            (reset_hovered_day, update_reset_controls).run_if(in_state(Day(0))),
+        );
+        app.add_observer(on_action);
+    }
+}
+
+/// While this exists clicking a day asks what to reset instead of opening it
+#[derive(Resource)]
+pub(crate) struct ResetMode;
+
+#[derive(Component)]
+struct ResetDialog;
+
+#[derive(Component)]
+struct ResetYearButton;
+
+#[derive(Component)]
+struct ResetModeLabel;
+
+#[derive(Component)]
+struct ResetYearLabel;
+
+#[derive(Component, Clone, Copy)]
+enum ResetAction {
+    ToggleMode,
+    /// Opens the dialog for every day of the open year
+    Year,
+    Confirm(ResetProgress),
+    Cancel,
+}
+
+fn spawn_reset_controls(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                right: Val::Px(10.0),
+                column_gap: Val::Px(6.0),
+                ..Default::default()
+            },
+            DespawnOnExit(Day(0)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|p| {
+            p.spawn(button(
+                ButtonProps::default(),
+                ResetAction::Year,
+                Spawn((Text::default(), ResetYearLabel)),
+            ))
+            .insert((
+                ResetYearButton,
+                Node {
+                    display: Display::None,
+                    ..Default::default()
+                },
+            ));
+            p.spawn(button(
+                ButtonProps::default(),
+                ResetAction::ToggleMode,
+                Spawn((Text::new("Reset..."), ResetModeLabel)),
+            ));
+        });
+}
+
+fn update_reset_controls(
+    (mode, year): (Option<Res<ResetMode>>, Res<Year>),
+    mut year_buttons: Query<&mut Node, With<ResetYearButton>>,
+    mut labels: Query<&mut Text, With<ResetModeLabel>>,
+    mut year_labels: Query<&mut Text, (With<ResetYearLabel>, Without<ResetModeLabel>)>,
+) {
+    // only the open year is reset, the label says which
+    let year_label = format!("Reset {}", year.year);
+    for mut text in &mut year_labels {
+        if text.0 != year_label {
+            text.0.clone_from(&year_label);
+        }
+    }
+    let on = mode.is_some();
+    for mut node in &mut year_buttons {
+        let display = if on { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    let label = if on {
+        "Pick a day to reset, or click to cancel"
+    } else {
+        "Reset..."
+    };
+    for mut text in &mut labels {
+        if text.0 != label {
+            text.0 = label.to_string();
+        }
+    }
+}
+
+/// R resets the hovered day, or the focused one when navigating with the keyboard
+fn reset_hovered_day(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    tiles: Query<(Entity, &Day, &Hovered), With<CalendarTile>>,
+    (focus, focus_visible): (Res<InputFocus>, Res<InputFocusVisible>),
+    dialogs: Query<(), With<ResetDialog>>,
+    mut commands: Commands,
+) {
+    let pressed = keys.just_pressed(KeyCode::KeyR)
+        || gamepads
+            .iter()
+            .any(|pad| pad.just_pressed(GamepadButton::West));
+    // R typed into a panel, like the export path, isn't a reset
+    let typing = focus.0.is_some_and(|focused| tiles.get(focused).is_err());
+    if !pressed || typing || !dialogs.is_empty() {
+        return;
+    }
+    let hovered = tiles.iter().find(|tile| tile.2.get());
+    let focused = focus
+        .0
+        .filter(|_| focus_visible.0)
+        .and_then(|focused| tiles.get(focused).ok());
+    if let Some((_, day, _)) = hovered.or(focused) {
+        commands.queue(ask_to_reset(Some(day.0)));
+    }
+}
+
+fn on_action(
+    event: On<bevy::ui_widgets::Activate>,
+    actions: Query<&ResetAction>,
+    mut commands: Commands,
+) {
+    let Ok(action) = actions.get(event.entity) else {
+        return;
+    };
+    match *action {
+        ResetAction::ToggleMode => commands.queue(|world: &mut World| {
+            if world.remove_resource::<ResetMode>().is_none() {
+                world.insert_resource(ResetMode);
+            }
+        }),
+        ResetAction::Year => commands.queue(ask_to_reset(None)),
+        ResetAction::Confirm(reset) => {
+            commands.queue(close_dialog);
+            commands.trigger(reset);
+        }
+        ResetAction::Cancel => commands.queue(close_dialog),
+    }
+}
+
+/// Opens the dialog to reset `day`, or every day of the open year when `None`
+pub(crate) fn ask_to_reset(day: Option<u8>) -> impl FnOnce(&mut World) {
+    move |world: &mut World| {
+        close_dialog(world);
+        let year = *world.resource::<Year>();
+        let calendar = world.resource::<CalendarState>();
+        let history = world.resource::<SubmissionHistory>();
+        let (question, choices) = match day {
+            None => (
+                format!(
+                    "Reset every star, best time and attempt of {}? This can't be undone.",
+                    year.year
+                ),
+                vec![("Reset the year", ResetProgress::all())],
+            ),
+            Some(day) => (
+                {
+                    let stars = calendar.stars(day);
+                    let attempts = history.attempts(day, Puzzle::Part1).len()
+                        + history.attempts(day, Puzzle::Part2).len();
+                    format!(
+                        "Reset day {}? It has {} star{} and {} attempt{}. This can't be undone.",
+                        day,
+                        stars,
+                        if stars == 1 { "" } else { "s" },
+                        attempts,
+                        if attempts == 1 { "" } else { "s" },
+                    )
+                },
+                vec![
+                    ("Part 1", ResetProgress::part(day, Puzzle::Part1)),
+                    ("Part 2", ResetProgress::part(day, Puzzle::Part2)),
+                    ("Both parts", ResetProgress::day(day)),
+                ],
+            ),
+        };
+        let dialog = world
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(50.),
+                    left: Val::Percent(25.),
+                    top: Val::Percent(30.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(10.),
+                    padding: UiRect::all(Val::Px(20.)),
+                    ..Default::default()
+                },
+                BorderRadius::all(Val::Px(15.)),
+                ThemeBackgroundColor(tokens::WINDOW_BG),
+                ThemeFontColor(tokens::TEXT_MAIN),
+                GlobalZIndex(5),
+                DespawnOnExit(Day(0)),
+                ResetDialog,
+            ))
+            .with_children(|p| {
+                p.spawn((Text::new(question), ThemedText));
+                p.spawn(Node {
+                    column_gap: Val::Px(8.),
+                    ..Default::default()
+                })
+                .with_children(|p| {
+                    for (label, reset) in choices {
+                        p.spawn(button(
+                            ButtonProps {
+                                variant: ButtonVariant::Primary,
+                                ..Default::default()
+                            },
+                            ResetAction::Confirm(reset),
+                            Spawn(Text::new(label)),
+                        ));
+                    }
+                    p.spawn(button(
+                        ButtonProps::default(),
+                        ResetAction::Cancel,
+                        Spawn(Text::new("Cancel")),
+                    ));
+                });
+            })
+            .id();
+        // keeps Enter and Space from activating the calendar tile behind the dialog
+        world.insert_resource(InputFocus(Some(dialog)));
+    }
+}
+
+fn close_dialog(world: &mut World) {
+    world.remove_resource::<ResetMode>();
+    let dialogs = world
+        .query_filtered::<Entity, With<ResetDialog>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    if dialogs.is_empty() {
+        return;
+    }
+    world.insert_resource(InputFocus(None));
+    for dialog in dialogs {
+        world.despawn(dialog);
+    }
+}
+
+#[test]
+fn reset_takes_stars_and_attempts() {
+    let mut calendar: CalendarState = serde_json::from_str(
+        r#"{"days": [{"puzzle1_completed": true, "puzzle2_completed": true}]}"#,
+    )
+    .unwrap();
+    let mut history: SubmissionHistory = serde_json::from_str(r#"{"days": {}}"#).unwrap();
+    history.record(1, Puzzle::Part2, 5u8.into(), crate::Results::Correct, None);
+    calendar.reset(1, Puzzle::Part2);
+    history.clear(1, Puzzle::Part2);
+    assert_eq!(calendar.stars(1), 1);
+    assert!(history.attempts(1, Puzzle::Part2).is_empty());
+    let mut timings: Timings = serde_json::from_str(r#"{"best": {}}"#).unwrap();
+    for step in [crate::Step::Parse, crate::Step::Part2] {
+        timings.record(
+            &crate::Timing {
+                day: 1,
+                step,
+                solver: crate::Solver::Reference,
+                duration: std::time::Duration::from_millis(3),
+            },
+            true,
+        );
+    }
+    timings.clear(1, Some(Puzzle::Part2));
+    assert!(timings.best(1).reference.part2.is_none());
+    assert!(timings.best(1).reference.parse.is_some());
+    timings.clear(1, None);
+    assert!(timings.best(1).reference.parse.is_none());
+    let year = Year::aoc(2025);
+    assert_eq!(ResetProgress::all().describe(&year), "every day of 2025");
+    assert_eq!(
+        ResetProgress::part(3, Puzzle::Part1).describe(&year),
+        "day 3 Part 1"
+    );
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{SaveStore, StoreError, Toast};
+
+/// A save kept in an envelope with its format version, so older saves can be migrated
+/// instead of failing to load.
+///
+/// Saves from before there were envelopes are version 0,
+/// saves that are still version 0 can be kept bare with [`SaveStore::set`] and still be loaded
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Bump this when the saved format changes, and add the step from the old version to [`Versioned::migrate`]
+    const VERSION: u32;
+
+    /// Turns a save of `version` into one of `version + 1`,
+    /// by default version 0 already looks like version 1 and there are no other steps
+    fn migrate(version: u32, save: Value) -> Result<Value, String> {
+        match version {
+            0 => Ok(save),
+            _ => Err(format!("no migration from version {}", version)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct RawEnvelope {
+    version: u32,
+    data: Value,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    /// Nothing is saved under the key
+    Missing,
+    /// The save isn't in a format this version can read
+    Unreadable(String),
+    /// Saved by a newer version of the game
+    TooNew { version: u32, supported: u32 },
+    /// A step of [`Versioned::migrate`] failed
+    Migration { version: u32, message: String },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Missing => write!(f, "nothing is saved"),
+            LoadError::Unreadable(e) => write!(f, "it can't be read: {}", e),
+            LoadError::TooNew { version, supported } => write!(
+                f,
+                "it is version {} but this build only reads up to version {}",
+                version, supported
+            ),
+            LoadError::Migration { version, message } => {
+                write!(
+                    f,
+                    "it couldn't be updated from version {}: {}",
+                    version, message
+                )
+            }
+        }
+    }
+}
+
+/// Takes the version off a saved value, migrating it up to `T`'s version
+fn upgrade<T: Versioned>(save: Value) -> Result<T, LoadError> {
+    let (mut version, mut data) = match serde_json::from_value::<RawEnvelope>(save.clone()) {
+        Ok(envelope) => (envelope.version, envelope.data),
+        Err(_) => (0, save),
+    };
+    if version > T::VERSION {
+        return Err(LoadError::TooNew {
+            version,
+            supported: T::VERSION,
+        });
+    }
+    while version < T::VERSION {
+        data = T::migrate(version, data)
+            .map_err(|message| LoadError::Migration { version, message })?;
+        version += 1;
+    }
+    serde_json::from_value(data).map_err(|e| LoadError::Unreadable(e.to_string()))
+}
+
+impl SaveStore {
+    /// Loads a [`Versioned`] save, migrating it if it is from an older version
+    pub fn load<T: Versioned>(&self, key: impl AsRef<str>) -> Result<T, LoadError> {
+        let save = match self.get::<Value>(key) {
+            Ok(save) => save,
+            Err(StoreError::NotFound) => return Err(LoadError::Missing),
+            Err(e) => return Err(LoadError::Unreadable(e.to_string())),
+        };
+        upgrade(save)
+    }
+
+    /// Saves `value` with its version so [`SaveStore::load`] can migrate it later
+    pub fn store<T: Versioned>(
+        &mut self,
+        key: impl AsRef<str>,
+        value: &T,
+    ) -> Result<(), StoreError> {
+        self.set(
+            key,
+            &Envelope {
+                version: T::VERSION,
+                data: value,
+            },
+        )
+    }
+}
+
+/// Save problems found while loading, shown as toasts once the game is running
+#[derive(Resource, Default)]
+pub(crate) struct SaveProblems(Vec<String>);
+
+/// Loads the save under `key`, a save that can't be loaded is kept under `{key}Unreadable`
+/// and reported rather than quietly replaced
+pub(crate) fn load_or_report<T: Versioned>(world: &mut World, key: String) -> Option<T> {
+    let mut saves = world.resource_mut::<SaveStore>();
+    let error = match saves.load(&key) {
+        Ok(save) => return Some(save),
+        Err(LoadError::Missing) => return None,
+        Err(e) => e,
+    };
+    let backup = format!("{}Unreadable", key);
+    let mut message = format!("Couldn't load the {} save, {}", key, error);
+    if let Ok(save) = saves.get::<Value>(&key)
+        && saves.set(&backup, &save).is_ok()
+    {
+        message.push_str(&format!("; it was kept as {}", backup));
+    }
+    error!("{}", message);
+    report_save_problem(world, message);
+    None
+}
+
+/// Shows `message` as a toast once the game is running
+pub(crate) fn report_save_problem(world: &mut World, message: String) {
+    world.get_resource_or_init::<SaveProblems>().0.push(message);
+}
+
+pub(crate) fn report_save_problems(mut problems: ResMut<SaveProblems>, mut commands: Commands) {
+    for problem in problems.0.drain(..) {
+        commands.trigger(Toast::error(problem));
+    }
+}
+
+#[test]
+fn saves_migrate_to_the_current_version() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Stars {
+        count: u8,
+    }
+    impl Versioned for Stars {
+        const VERSION: u32 = 2;
+        fn migrate(version: u32, save: Value) -> Result<Value, String> {
+            match version {
+                0 => Ok(save),
+                1 => Ok(serde_json::json!({ "count": save["stars"] })),
+                _ => Err(format!("no migration from version {}", version)),
+            }
+        }
+    }
+    let legacy = serde_json::json!({ "stars": 3 });
+    assert_eq!(upgrade::<Stars>(legacy).unwrap(), Stars { count: 3 });
+    let current = serde_json::json!({ "version": 2, "data": { "count": 4 } });
+    assert_eq!(upgrade::<Stars>(current).unwrap(), Stars { count: 4 });
+    let newer = serde_json::json!({ "version": 3, "data": {} });
+    assert!(matches!(
+        upgrade::<Stars>(newer),
+        Err(LoadError::TooNew { version: 3, .. })
+    ));
+    let broken = serde_json::json!({ "version": 2, "data": { "count": "many" } });
+    assert!(matches!(
+        upgrade::<Stars>(broken),
+        Err(LoadError::Unreadable(_))
+    ));
+}
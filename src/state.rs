@@ -1,12 +1,22 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default, Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+#[derive(Default, Debug, Hash, PartialEq, Eq, Clone, Copy, SystemSet, Serialize, Deserialize)]
 pub enum Puzzle {
     #[default]
     Part1,
     Part2,
 }
 
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Puzzle::Part1 => write!(f, "Part 1"),
+            Puzzle::Part2 => write!(f, "Part 2"),
+        }
+    }
+}
+
 #[derive(States, Debug, Hash, PartialEq, Eq, Clone, Component, Default, Copy, Event)]
 pub struct Day(pub u8);
 
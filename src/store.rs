@@ -0,0 +1,245 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use bevy::prelude::*;
+use serde_json::Value;
+
+/// Where saves are kept, [`SaveStore`](crate::SaveStore) sits on top of one and keeps profiles apart.
+///
+/// Pick one with [`StoreBackend`] in [`AoCSettings`](crate::AoCSettings) or [`HeadlessPlugin`](crate::HeadlessPlugin)
+pub trait ProgressStore: Send + Sync + 'static {
+    fn get(&self, key: &str) -> Result<Value, StoreError>;
+    fn set(&mut self, key: &str, value: Value) -> Result<(), StoreError>;
+    /// Removing a key that isn't there isn't an error
+    fn remove(&mut self, key: &str) -> Result<(), StoreError>;
+    /// Every key something is saved under
+    fn keys(&self) -> Result<Vec<String>, StoreError>;
+
+    /// Removes every key starting with `prefix`, e.g. all of a profile's saves
+    fn remove_prefix(&mut self, prefix: &str) -> Result<(), StoreError> {
+        for key in self.keys()? {
+            if key.starts_with(prefix) {
+                self.remove(&key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum StoreError {
+    /// Nothing is saved under the key
+    NotFound,
+    /// The backend couldn't read or write
+    Backend(String),
+    /// The save isn't the type it was read or written as
+    Format(String),
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::NotFound => write!(f, "nothing is saved"),
+            StoreError::Backend(e) => write!(f, "{}", e),
+            StoreError::Format(e) => write!(f, "wrong format: {}", e),
+        }
+    }
+}
+
+/// Which [`ProgressStore`] saves go to
+#[derive(Debug, Clone, Default)]
+pub enum StoreBackend {
+    /// The user's [`PkvStore`](bevy_pkv::PkvStore), shared by every run
+    #[default]
+    Pkv,
+    /// One JSON file, see [`JsonFileStore`]
+    JsonFile(PathBuf),
+    /// Nothing is kept after the app closes, for tests and one-off runs
+    Memory,
+}
+
+impl StoreBackend {
+    pub fn open(&self) -> Box<dyn ProgressStore> {
+        match self {
+            StoreBackend::Pkv => {
+                Box::new(IndexedPkvStore::new(bevy_pkv::PkvStore::new("Phox", "AoB")))
+            }
+            StoreBackend::JsonFile(path) => match JsonFileStore::open(path.clone()) {
+                Ok(store) => Box::new(store),
+                Err(e) => {
+                    // writing over a file that couldn't be read would lose it
+                    error!("{}, nothing will be saved", e);
+                    Box::new(MemoryStore::default())
+                }
+            },
+            StoreBackend::Memory => Box::new(MemoryStore::default()),
+        }
+    }
+}
+
+/// A [`PkvStore`](bevy_pkv::PkvStore) can't list its keys, so the ones set through here are kept under [`INDEX_KEY`]
+struct IndexedPkvStore {
+    pkv: bevy_pkv::PkvStore,
+    keys: BTreeSet<String>,
+}
+
+const INDEX_KEY: &str = "StoreKeys";
+
+impl IndexedPkvStore {
+    fn new(pkv: bevy_pkv::PkvStore) -> IndexedPkvStore {
+        let keys = pkv.get(INDEX_KEY).unwrap_or_default();
+        IndexedPkvStore { pkv, keys }
+    }
+
+    fn save_index(&mut self) -> Result<(), StoreError> {
+        self.pkv
+            .set(INDEX_KEY, &self.keys)
+            .map_err(|e| StoreError::Backend(e.to_string()))
+    }
+}
+
+impl ProgressStore for IndexedPkvStore {
+    fn get(&self, key: &str) -> Result<Value, StoreError> {
+        match self.pkv.get(key) {
+            Ok(value) => Ok(value),
+            Err(bevy_pkv::GetError::NotFound) => Err(StoreError::NotFound),
+            Err(e) => Err(StoreError::Backend(e.to_string())),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), StoreError> {
+        self.pkv
+            .set(key, &value)
+            .map_err(|e| StoreError::Backend(e.to_string()))?;
+        if self.keys.insert(key.to_string()) {
+            self.save_index()?;
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StoreError> {
+        self.pkv
+            .remove(key)
+            .map_err(|e| StoreError::Backend(e.to_string()))?;
+        if self.keys.remove(key) {
+            self.save_index()?;
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StoreError> {
+        Ok(self.keys.iter().cloned().collect())
+    }
+}
+
+/// Every save in one readable JSON file, written again on every change
+pub struct JsonFileStore {
+    path: PathBuf,
+    saves: BTreeMap<String, Value>,
+}
+
+impl JsonFileStore {
+    /// Reads the saves in `path`, a file that isn't there yet starts empty
+    pub fn open(path: PathBuf) -> Result<JsonFileStore, String> {
+        let saves = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("Failed to read saves in {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("Failed to open {}: {}", path.display(), e)),
+        };
+        Ok(JsonFileStore { path, saves })
+    }
+
+    fn write(&self) -> Result<(), StoreError> {
+        let write = || -> Result<(), String> {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            let text = serde_json::to_string_pretty(&self.saves).map_err(|e| e.to_string())?;
+            std::fs::write(&self.path, text).map_err(|e| e.to_string())
+        };
+        write().map_err(|e| {
+            StoreError::Backend(format!("Failed to write {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+impl ProgressStore for JsonFileStore {
+    fn get(&self, key: &str) -> Result<Value, StoreError> {
+        self.saves.get(key).cloned().ok_or(StoreError::NotFound)
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), StoreError> {
+        self.saves.insert(key.to_string(), value);
+        self.write()
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StoreError> {
+        if self.saves.remove(key).is_some() {
+            self.write()?;
+        }
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StoreError> {
+        Ok(self.saves.keys().cloned().collect())
+    }
+
+    /// Writes the file once rather than for every key
+    fn remove_prefix(&mut self, prefix: &str) -> Result<(), StoreError> {
+        let before = self.saves.len();
+        self.saves.retain(|key, _| !key.starts_with(prefix));
+        if self.saves.len() != before {
+            self.write()?;
+        }
+        Ok(())
+    }
+}
+
+/// Saves that only last as long as the app
+#[derive(Default)]
+pub struct MemoryStore(pub BTreeMap<String, Value>);
+
+impl ProgressStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Value, StoreError> {
+        self.0.get(key).cloned().ok_or(StoreError::NotFound)
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), StoreError> {
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StoreError> {
+        self.0.remove(key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StoreError> {
+        Ok(self.0.keys().cloned().collect())
+    }
+}
+
+#[test]
+fn json_file_store_keeps_saves() {
+    let path = std::env::temp_dir().join("aob_json_file_store_keeps_saves/saves.json");
+    _ = std::fs::remove_file(&path);
+    let mut store = JsonFileStore::open(path.clone()).unwrap();
+    assert!(matches!(store.get("Seed"), Err(StoreError::NotFound)));
+    store.set("Seed", 7.into()).unwrap();
+    store.set("Year", 2025.into()).unwrap();
+    store.remove("Year").unwrap();
+    store.remove("Year").unwrap();
+    store.set("Profile1/Seed", 8.into()).unwrap();
+    store.set("Profile1/History2025", 9.into()).unwrap();
+    store.remove_prefix("Profile1/").unwrap();
+    let store = JsonFileStore::open(path.clone()).unwrap();
+    assert_eq!(store.get("Seed").unwrap(), 7);
+    assert!(matches!(store.get("Year"), Err(StoreError::NotFound)));
+    assert_eq!(store.keys().unwrap(), ["Seed"]);
+    std::fs::write(&path, "not json").unwrap();
+    assert!(JsonFileStore::open(path.clone()).is_err());
+    _ = std::fs::remove_file(&path);
+}
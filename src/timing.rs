@@ -0,0 +1,548 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bevy::{
+    feathers::{
+        controls::{ButtonProps, button},
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    input_focus::InputFocus,
+    platform::time::Instant,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Day, DayTitles, InputSelection, PKVKeys, Puzzle, SaveStore, Year,
+    save::{Versioned, load_or_report},
+};
+
+/// A timed part of solving a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::Part1, Step::Part2];
+}
+
+impl From<Puzzle> for Step {
+    fn from(puzzle: Puzzle) -> Step {
+        match puzzle {
+            Puzzle::Part1 => Step::Part1,
+            Puzzle::Part2 => Step::Part2,
+        }
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "Parse"),
+            Step::Part1 => write!(f, "Part 1"),
+            Step::Part2 => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// Whose code was timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Solver {
+    /// The [`AocDay`](crate::AocDay) the day was added with, timed when the day opens
+    Reference,
+    /// Your own systems, timed from the day opening to your first [`Submit`](crate::Submit) of each part,
+    /// or by [`Timing::measure`] for just your code
+    Yours,
+}
+
+/// Trigger to record how long a step of a day took, the best times on the real input are saved
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Timing {
+    pub day: u8,
+    pub step: Step,
+    pub solver: Solver,
+    pub duration: Duration,
+}
+
+impl Timing {
+    /// Runs `solve` and times it as [`Solver::Yours`], trigger the timing to record it
+    pub fn measure<T>(day: u8, step: Step, solve: impl FnOnce() -> T) -> (T, Timing) {
+        let start = Instant::now();
+        let value = solve();
+        let timing = Timing {
+            day,
+            step,
+            solver: Solver::Yours,
+            duration: start.elapsed(),
+        };
+        (value, timing)
+    }
+}
+
+/// How long each step took, `None` if it hasn't been timed
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct StepTimes {
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl StepTimes {
+    pub fn get(&self, step: Step) -> Option<Duration> {
+        match step {
+            Step::Parse => self.parse,
+            Step::Part1 => self.part1,
+            Step::Part2 => self.part2,
+        }
+    }
+
+    fn get_mut(&mut self, step: Step) -> &mut Option<Duration> {
+        match step {
+            Step::Parse => &mut self.parse,
+            Step::Part1 => &mut self.part1,
+            Step::Part2 => &mut self.part2,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Step::ALL.iter().all(|step| self.get(*step).is_none())
+    }
+
+    /// Keeps the faster time of each step
+    fn merge(&mut self, other: StepTimes) {
+        for step in Step::ALL {
+            let ours = self.get_mut(step);
+            *ours = match (*ours, other.get(step)) {
+                (Some(ours), Some(theirs)) => Some(ours.min(theirs)),
+                (ours, theirs) => ours.or(theirs),
+            };
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub reference: StepTimes,
+    pub yours: StepTimes,
+}
+
+impl DayTimings {
+    pub fn get(&self, solver: Solver) -> &StepTimes {
+        match solver {
+            Solver::Reference => &self.reference,
+            Solver::Yours => &self.yours,
+        }
+    }
+
+    fn get_mut(&mut self, solver: Solver) -> &mut StepTimes {
+        match solver {
+            Solver::Reference => &mut self.reference,
+            Solver::Yours => &mut self.yours,
+        }
+    }
+}
+
+/// Solve times for the days of the open [`Year`], the best are kept under [`PKVKeys::Timings`]
+#[derive(Resource, Debug, Serialize, Deserialize)]
+pub struct Timings {
+    best: BTreeMap<u8, DayTimings>,
+    /// The last time each step ran, on whichever input was open
+    #[serde(skip)]
+    latest: BTreeMap<u8, DayTimings>,
+}
+
+impl Versioned for Timings {
+    const VERSION: u32 = 1;
+}
+
+impl FromWorld for Timings {
+    fn from_world(world: &mut World) -> Self {
+        let key = world.resource::<Year>().key(PKVKeys::Timings);
+        load_or_report(world, key).unwrap_or_else(|| Timings {
+            best: BTreeMap::new(),
+            latest: BTreeMap::new(),
+        })
+    }
+}
+
+impl Timings {
+    pub fn best(&self, day: u8) -> DayTimings {
+        self.best.get(&day).copied().unwrap_or_default()
+    }
+
+    pub fn latest(&self, day: u8) -> DayTimings {
+        self.latest.get(&day).copied().unwrap_or_default()
+    }
+
+    /// Keeps `timing` as the latest, and as the best if `real` and faster, returns if it was the best
+    pub fn record(&mut self, timing: &Timing, real: bool) -> bool {
+        *self
+            .latest
+            .entry(timing.day)
+            .or_default()
+            .get_mut(timing.solver)
+            .get_mut(timing.step) = Some(timing.duration);
+        if !real {
+            return false;
+        }
+        let best = self
+            .best
+            .entry(timing.day)
+            .or_default()
+            .get_mut(timing.solver)
+            .get_mut(timing.step);
+        if best.is_some_and(|best| best <= timing.duration) {
+            return false;
+        }
+        *best = Some(timing.duration);
+        true
+    }
+
+    /// Keeps the best time of each step from both
+    pub fn merge(&mut self, other: Timings) {
+        for (day, theirs) in other.best {
+            let ours = self.best.entry(day).or_default();
+            ours.reference.merge(theirs.reference);
+            ours.yours.merge(theirs.yours);
+        }
+    }
+
+    /// Forgets the best times of a part, or of the whole day when `None`
+    pub fn clear(&mut self, day: u8, puzzle: Option<Puzzle>) {
+        let Some(puzzle) = puzzle else {
+            self.best.remove(&day);
+            return;
+        };
+        let step = Step::from(puzzle);
+        if let Some(best) = self.best.get_mut(&day) {
+            for solver in [Solver::Reference, Solver::Yours] {
+                *best.get_mut(solver).get_mut(step) = None;
+            }
+        }
+    }
+}
+
+/// Shortest readable form of a solve time, e.g. `850µs` or `12.3ms`
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.;
+    if micros < 1_000. {
+        format!("{:.0}µs", micros)
+    } else if micros < 1_000_000. {
+        format!("{:.1}ms", micros / 1_000.)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.)
+    }
+}
+
+fn show(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), format_duration)
+}
+
+/// When the open day's reference solve finished, to time [`Solver::Yours`] up to each part's first submit
+#[derive(Resource)]
+pub(crate) struct SolveClock {
+    day: u8,
+    opened: Instant,
+    /// Parts already timed since the day opened
+    timed: Vec<Step>,
+}
+
+impl SolveClock {
+    pub(crate) fn start(day: u8) -> SolveClock {
+        SolveClock {
+            day,
+            opened: Instant::now(),
+            timed: Vec::new(),
+        }
+    }
+
+    /// How long `day` was open before `puzzle` was first submitted, `None` once it has been timed
+    pub(crate) fn submitted(&mut self, day: u8, puzzle: Puzzle) -> Option<Timing> {
+        let step = Step::from(puzzle);
+        if day != self.day || self.timed.contains(&step) {
+            return None;
+        }
+        self.timed.push(step);
+        Some(Timing {
+            day,
+            step,
+            solver: Solver::Yours,
+            duration: self.opened.elapsed(),
+        })
+    }
+}
+
+pub(crate) fn record_timing(
+    timing: On<Timing>,
+    mut timings: ResMut<Timings>,
+    (year, selection): (Res<Year>, Res<InputSelection>),
+    mut saves: ResMut<SaveStore>,
+    clock: Option<ResMut<SolveClock>>,
+) {
+    // a step timed with Timing::measure isn't timed again when it's submitted
+    if timing.solver == Solver::Yours
+        && let Some(mut clock) = clock
+        && clock.day == timing.day
+        && !clock.timed.contains(&timing.step)
+    {
+        clock.timed.push(timing.step);
+    }
+    debug!(
+        "Day {} {} ({:?}) took {}",
+        timing.day,
+        timing.step,
+        timing.solver,
+        format_duration(timing.duration)
+    );
+    if timings.record(&timing, selection.is_real(timing.day))
+        && let Err(e) = saves.store(year.key(PKVKeys::Timings), &*timings)
+    {
+        error!("Failed to save timings: {}", e);
+    }
+}
+
+/// Shows solve times while a day is open, and a table of the best on the calendar
+pub struct TimingPlugin;
+
+impl Plugin for TimingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(Day(0)), spawn_day_timings);
+        app.add_systems(OnEnter(Day(0)), spawn_summary_button);
+        app.add_systems(Update, update_day_timings.run_if(not(in_state(Day(0)))));
+        app.add_observer(on_action);
+    }
+}
+
+#[derive(Component)]
+struct DayTimingsText;
+
+#[derive(Component)]
+struct SummaryPanel;
+
+#[derive(Component, Clone, Copy)]
+enum TimingAction {
+    ToggleSummary,
+}
+
+fn spawn_day_timings(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.),
+            left: Val::Px(10.),
+            padding: UiRect::all(Val::Px(8.)),
+            ..Default::default()
+        },
+        BorderRadius::all(Val::Px(8.)),
+        ThemeBackgroundColor(tokens::WINDOW_BG),
+        ThemeFontColor(tokens::TEXT_MAIN),
+        GlobalZIndex(1),
+        DespawnOnEnter(Day(0)),
+        Pickable::IGNORE,
+        children![(
+            Text::default(),
+            TextFont::from_font_size(13.),
+            ThemedText,
+            DayTimingsText,
+        )],
+    ));
+}
+
+fn update_day_timings(
+    day: Res<State<Day>>,
+    timings: Res<Timings>,
+    mut texts: Query<&mut Text, With<DayTimingsText>>,
+) {
+    let day = day.0;
+    let (latest, best) = (timings.latest(day), timings.best(day));
+    let mut lines = Vec::new();
+    for (solver, name) in [(Solver::Reference, "Reference"), (Solver::Yours, "Yours")] {
+        let (latest, best) = (latest.get(solver), best.get(solver));
+        if solver == Solver::Yours && latest.is_empty() && best.is_empty() {
+            continue;
+        }
+        lines.push(format!("{}: latest (best)", name));
+        for step in Step::ALL {
+            lines.push(format!(
+                "  {:<7}{} ({})",
+                step.to_string(),
+                show(latest.get(step)),
+                show(best.get(step))
+            ));
+        }
+    }
+    let text = lines.join("\n");
+    for mut shown in &mut texts {
+        if shown.0 != text {
+            shown.0 = text.clone();
+        }
+    }
+}
+
+fn spawn_summary_button(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(50.0),
+            left: Val::Px(10.0),
+            ..Default::default()
+        },
+        DespawnOnExit(Day(0)),
+        GlobalZIndex(1),
+        children![button(
+            ButtonProps::default(),
+            TimingAction::ToggleSummary,
+            Spawn(Text::new("Timings")),
+        )],
+    ));
+}
+
+fn on_action(
+    event: On<bevy::ui_widgets::Activate>,
+    actions: Query<&TimingAction>,
+    mut commands: Commands,
+) {
+    if let Ok(TimingAction::ToggleSummary) = actions.get(event.entity) {
+        commands.queue(toggle_summary);
+    }
+}
+
+/// The table of best times for every day of the open year, closed if it was open
+fn toggle_summary(world: &mut World) {
+    let open = world
+        .query_filtered::<Entity, With<SummaryPanel>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    if !open.is_empty() {
+        for panel in open {
+            world.despawn(panel);
+        }
+        world.insert_resource(InputFocus(None));
+        return;
+    }
+    let year = *world.resource::<Year>();
+    let timings = world.resource::<Timings>();
+    let titles = world.resource::<DayTitles>();
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Parse".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+        "Yours 1".to_string(),
+        "Yours 2".to_string(),
+    ]];
+    for day in year.days().filter(|day| titles.contains(year.year, *day)) {
+        let best = timings.best(day);
+        rows.push([
+            day.to_string(),
+            show(best.reference.parse),
+            show(best.reference.part1),
+            show(best.reference.part2),
+            show(best.yours.part1),
+            show(best.yours.part2),
+        ]);
+    }
+    let columns = rows[0].len() as u16;
+    let panel = world
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.),
+                top: Val::Percent(15.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.),
+                padding: UiRect::all(Val::Px(20.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(15.)),
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(5),
+            DespawnOnExit(Day(0)),
+            SummaryPanel,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(format!("Best times for {} on the real inputs", year.year)),
+                ThemedText,
+            ));
+            p.spawn(Node {
+                display: Display::Grid,
+                grid_template_columns: RepeatedGridTrack::auto(columns),
+                column_gap: Val::Px(16.),
+                row_gap: Val::Px(4.),
+                ..Default::default()
+            })
+            .with_children(|p| {
+                for cell in rows.into_iter().flatten() {
+                    p.spawn((Text::new(cell), TextFont::from_font_size(14.), ThemedText));
+                }
+            });
+            p.spawn(button(
+                ButtonProps::default(),
+                TimingAction::ToggleSummary,
+                Spawn(Text::new("Close")),
+            ));
+        })
+        .id();
+    // keeps Enter and Space from activating the calendar tile behind the table
+    world.insert_resource(InputFocus(Some(panel)));
+}
+
+#[test]
+fn best_times_only_come_from_real_inputs() {
+    let mut timings = Timings {
+        best: BTreeMap::new(),
+        latest: BTreeMap::new(),
+    };
+    let timing = |step, millis| Timing {
+        day: 3,
+        step,
+        solver: Solver::Reference,
+        duration: Duration::from_millis(millis),
+    };
+    assert!(timings.record(&timing(Step::Part1, 20), true));
+    assert!(!timings.record(&timing(Step::Part1, 30), true));
+    assert!(!timings.record(&timing(Step::Part1, 1), false));
+    assert!(timings.record(&timing(Step::Part1, 10), true));
+    assert_eq!(
+        timings.best(3).reference.part1,
+        Some(Duration::from_millis(10))
+    );
+    assert_eq!(
+        timings.latest(3).reference.part1,
+        Some(Duration::from_millis(10))
+    );
+    assert!(timings.best(3).yours.is_empty());
+    let (answer, yours) = Timing::measure(3, Step::Part2, || 42);
+    assert_eq!((answer, yours.solver), (42, Solver::Yours));
+    let mut clock = SolveClock::start(3);
+    assert!(clock.submitted(4, Puzzle::Part1).is_none());
+    let submitted = clock.submitted(3, Puzzle::Part1).unwrap();
+    assert_eq!(
+        (submitted.step, submitted.solver),
+        (Step::Part1, Solver::Yours)
+    );
+    assert!(clock.submitted(3, Puzzle::Part1).is_none());
+    let mut imported = Timings {
+        best: BTreeMap::new(),
+        latest: BTreeMap::new(),
+    };
+    imported.record(&timing(Step::Part1, 5), true);
+    imported.record(&timing(Step::Part2, 40), true);
+    timings.merge(imported);
+    assert_eq!(
+        timings.best(3).reference.part1,
+        Some(Duration::from_millis(5))
+    );
+    assert_eq!(
+        timings.best(3).reference.part2,
+        Some(Duration::from_millis(40))
+    );
+    assert_eq!(format_duration(Duration::from_micros(850)), "850µs");
+    assert_eq!(format_duration(Duration::from_micros(12_340)), "12.3ms");
+    assert_eq!(format_duration(Duration::from_millis(1_240)), "1.24s");
+}
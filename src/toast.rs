@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use bevy::{
+    color::palettes::css,
+    feathers::{
+        palette,
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemeToken, ThemedText, UiTheme},
+        tokens,
+    },
+    prelude::*,
+};
+
+use crate::Results;
+
+pub const TOAST_CORRECT: ThemeToken = ThemeToken::new_static("aob.toast.correct");
+pub const TOAST_HIGHER: ThemeToken = ThemeToken::new_static("aob.toast.higher");
+pub const TOAST_LOWER: ThemeToken = ThemeToken::new_static("aob.toast.lower");
+pub const TOAST_WRONG: ThemeToken = ThemeToken::new_static("aob.toast.wrong");
+pub const TOAST_MISSING: ThemeToken = ThemeToken::new_static("aob.toast.missing");
+pub const TOAST_INFO: ThemeToken = ThemeToken::new_static("aob.toast.info");
+pub const TOAST_ERROR: ThemeToken = ThemeToken::new_static("aob.toast.error");
+
+/// How many toasts can be on screen at once, the oldest is dropped first
+const MAX_TOASTS: usize = 5;
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (add_toast_colors, spawn_toast_stack));
+        app.add_systems(Update, expire_toasts);
+        app.add_observer(spawn_toast);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Correct,
+    /// The answer was wrong and the correct answer is higher
+    WrongHigher,
+    /// The answer was wrong and the correct answer is lower
+    WrongLower,
+    /// The answer was wrong and there is no higher/lower hint
+    Wrong,
+    /// There is no stored answer to check against
+    Missing,
+    Info,
+    Error,
+}
+
+impl ToastKind {
+    pub fn token(&self) -> ThemeToken {
+        match self {
+            ToastKind::Correct => TOAST_CORRECT,
+            ToastKind::WrongHigher => TOAST_HIGHER,
+            ToastKind::WrongLower => TOAST_LOWER,
+            ToastKind::Wrong => TOAST_WRONG,
+            ToastKind::Missing => TOAST_MISSING,
+            ToastKind::Info => TOAST_INFO,
+            ToastKind::Error => TOAST_ERROR,
+        }
+    }
+}
+
+impl From<Results> for ToastKind {
+    fn from(result: Results) -> Self {
+        match result {
+            Results::Missing => ToastKind::Missing,
+            Results::Correct => ToastKind::Correct,
+            Results::Lower => ToastKind::WrongLower,
+            Results::Higher => ToastKind::WrongHigher,
+            Results::Incorrect => ToastKind::Wrong,
+        }
+    }
+}
+
+/// Trigger this to show a message in the corner of the screen
+#[derive(Event, Debug, Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    pub duration: Duration,
+}
+
+impl Toast {
+    pub fn new(kind: ToastKind, message: impl Into<String>) -> Self {
+        Toast {
+            kind,
+            message: message.into(),
+            duration: Duration::from_secs(4),
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Toast::new(ToastKind::Info, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Toast::new(ToastKind::Error, message)
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component)]
+struct ToastTimer(Timer);
+
+/// Only fills in colors the theme doesn't already have so a custom [`UiTheme`] can restyle toasts
+fn add_toast_colors(mut theme: ResMut<UiTheme>) {
+    let colors = [
+        (TOAST_CORRECT, palette::Y_AXIS),
+        (TOAST_HIGHER, palette::X_AXIS),
+        (TOAST_LOWER, palette::Z_AXIS),
+        (TOAST_WRONG, Color::from(css::DARK_RED)),
+        (TOAST_MISSING, palette::WARM_GRAY_1),
+        (TOAST_INFO, palette::GRAY_3),
+        (TOAST_ERROR, Color::from(css::DARK_RED)),
+    ];
+    for (token, color) in colors {
+        if !theme.0.color.contains_key(&token) {
+            theme.0.color.insert(token, color);
+        }
+    }
+}
+
+fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.),
+            right: Val::Px(20.),
+            width: Val::Px(360.),
+            flex_direction: FlexDirection::ColumnReverse,
+            row_gap: Val::Px(8.),
+            ..Default::default()
+        },
+        Pickable::IGNORE,
+        GlobalZIndex(10),
+        ToastStack,
+    ));
+}
+
+fn spawn_toast(
+    toast: On<Toast>,
+    stack: Single<(Entity, Option<&Children>), With<ToastStack>>,
+    mut commands: Commands,
+) {
+    let (stack, toasts) = *stack;
+    if let Some(toasts) = toasts
+        && toasts.len() >= MAX_TOASTS
+    {
+        for old in toasts.iter().take(toasts.len() + 1 - MAX_TOASTS) {
+            commands.entity(old).despawn();
+        }
+    }
+    commands.spawn((
+        Node {
+            padding: UiRect::axes(Val::Px(14.), Val::Px(10.)),
+            ..Default::default()
+        },
+        BorderRadius::all(Val::Px(8.)),
+        ThemeBackgroundColor(toast.kind.token()),
+        ThemeFontColor(tokens::TEXT_MAIN),
+        ToastTimer(Timer::new(toast.duration, TimerMode::Once)),
+        ChildOf(stack),
+        children![(Text::new(toast.message.clone()), ThemedText)],
+    ));
+}
+
+fn expire_toasts(
+    time: Res<Time>,
+    mut toasts: Query<(Entity, &mut ToastTimer)>,
+    mut commands: Commands,
+) {
+    for (entity, mut timer) in &mut toasts {
+        if timer.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,872 @@
+use std::{collections::BTreeMap, path::Path, path::PathBuf};
+
+use bevy::{
+    asset::ron,
+    feathers::{
+        controls::{ButtonProps, ButtonVariant, button},
+        theme::{ThemeBackgroundColor, ThemeFontColor, ThemedText},
+        tokens,
+    },
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    input_focus::InputFocus,
+    prelude::*,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    CalendarLayout, Cooldowns, Day, ManualAnswers, PKVKeys, Puzzle, SaveStore, StoreError,
+    SubmissionHistory, Timings, Toast, Year, Years, calendar::CalendarState,
+};
+
+/// Bumped when [`SaveFile`] changes in a way older versions can't read
+pub const SAVE_VERSION: u32 = 1;
+
+/// Conflicts listed in the import preview before the rest are counted
+const PREVIEW_CONFLICTS: usize = 12;
+
+/// Progress and settings of every year, written to a file to move them to another machine.
+///
+/// Files ending in `.ron` are RON, anything else is JSON
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub exported: DateTime<Utc>,
+    seed: Option<u64>,
+    layout: Option<CalendarLayout>,
+    /// The year that was open
+    year: Option<i32>,
+    years: BTreeMap<i32, YearSave>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct YearSave {
+    calendar: Option<CalendarState>,
+    history: Option<SubmissionHistory>,
+    manual_answers: Option<ManualAnswers>,
+    cooldowns: Option<Cooldowns>,
+    timings: Option<Timings>,
+}
+
+impl YearSave {
+    fn read(pkv: &SaveStore, year: &Year) -> YearSave {
+        YearSave {
+            calendar: pkv.load(year.key(PKVKeys::CalendarState)).ok(),
+            history: pkv.load(year.key(PKVKeys::History)).ok(),
+            manual_answers: pkv.load(year.key(PKVKeys::ManualAnswers)).ok(),
+            cooldowns: pkv.load(year.key(PKVKeys::Cooldowns)).ok(),
+            timings: pkv.load(year.key(PKVKeys::Timings)).ok(),
+        }
+    }
+
+    fn write(&self, pkv: &mut SaveStore, year: &Year) -> Result<(), String> {
+        write_key(
+            pkv,
+            year.key(PKVKeys::CalendarState),
+            self.calendar.as_ref(),
+            SaveStore::store,
+        )?;
+        write_key(
+            pkv,
+            year.key(PKVKeys::History),
+            self.history.as_ref(),
+            SaveStore::store,
+        )?;
+        write_key(
+            pkv,
+            year.key(PKVKeys::ManualAnswers),
+            self.manual_answers.as_ref(),
+            SaveStore::store,
+        )?;
+        write_key(
+            pkv,
+            year.key(PKVKeys::Cooldowns),
+            self.cooldowns.as_ref(),
+            SaveStore::store,
+        )?;
+        write_key(
+            pkv,
+            year.key(PKVKeys::Timings),
+            self.timings.as_ref(),
+            SaveStore::store,
+        )
+    }
+
+    fn merge(&mut self, other: YearSave) {
+        merge_into(&mut self.calendar, other.calendar, |ours, theirs| {
+            ours.merge(&theirs)
+        });
+        merge_into(&mut self.history, other.history, SubmissionHistory::merge);
+        merge_into(
+            &mut self.manual_answers,
+            other.manual_answers,
+            ManualAnswers::merge,
+        );
+        merge_into(&mut self.cooldowns, other.cooldowns, Cooldowns::merge);
+        merge_into(&mut self.timings, other.timings, Timings::merge);
+    }
+}
+
+fn merge_into<T>(ours: &mut Option<T>, theirs: Option<T>, merge: impl FnOnce(&mut T, T)) {
+    match (ours.as_mut(), theirs) {
+        (Some(ours), Some(theirs)) => merge(ours, theirs),
+        (None, theirs) => *ours = theirs,
+        (Some(_), None) => {}
+    }
+}
+
+/// Saves `value` under `key` with `save`, or removes the key if there is nothing to save.
+/// Merging and overwriting both write through here, versioned saves with [`SaveStore::store`]
+fn write_key<T>(
+    pkv: &mut SaveStore,
+    key: String,
+    value: Option<&T>,
+    save: impl FnOnce(&mut SaveStore, String, &T) -> Result<(), StoreError>,
+) -> Result<(), String> {
+    match value {
+        Some(value) => {
+            save(pkv, key.clone(), value).map_err(|e| format!("Failed to save {}: {}", key, e))
+        }
+        None => {
+            _ = pkv.remove(&key);
+            Ok(())
+        }
+    }
+}
+
+/// What to do with progress that is both here and in an imported file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the stars and attempts of both, settings and manual answers here win
+    Merge,
+    /// Replace everything here with the file
+    Overwrite,
+}
+
+impl SaveFile {
+    /// Everything saved for the years in `years`
+    pub fn read(pkv: &SaveStore, years: &Years) -> SaveFile {
+        SaveFile {
+            version: SAVE_VERSION,
+            exported: Utc::now(),
+            seed: pkv.get(PKVKeys::Seed).ok(),
+            layout: pkv.get(PKVKeys::CalendarLayout).ok(),
+            year: pkv.get(PKVKeys::Year).ok(),
+            years: years
+                .0
+                .values()
+                .map(|year| (year.year, YearSave::read(pkv, year)))
+                .collect(),
+        }
+    }
+
+    /// Saves everything in the file for the years in `years`, years it doesn't have are cleared
+    pub fn write(&self, pkv: &mut SaveStore, years: &Years) -> Result<(), String> {
+        if let Some(seed) = self.seed {
+            write_key(
+                pkv,
+                PKVKeys::Seed.as_ref().to_string(),
+                Some(&seed),
+                SaveStore::set,
+            )?;
+        }
+        if let Some(layout) = self.layout {
+            write_key(
+                pkv,
+                PKVKeys::CalendarLayout.as_ref().to_string(),
+                Some(&layout),
+                SaveStore::set,
+            )?;
+        }
+        if let Some(year) = self.year.filter(|year| years.get(*year).is_some()) {
+            write_key(
+                pkv,
+                PKVKeys::Year.as_ref().to_string(),
+                Some(&year),
+                SaveStore::set,
+            )?;
+        }
+        let empty = YearSave::default();
+        for year in years.0.values() {
+            self.years
+                .get(&year.year)
+                .unwrap_or(&empty)
+                .write(pkv, year)?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = if is_ron(path) {
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map_err(|e| e.to_string())
+        } else {
+            serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+        };
+        text.and_then(|text| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(path, text).map_err(|e| e.to_string())
+        })
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<SaveFile, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let version = parse::<FileVersion>(path, &text)?.version;
+        if version > SAVE_VERSION {
+            return Err(format!(
+                "{} is from a newer version (save version {}, this reads up to {})",
+                path.display(),
+                version,
+                SAVE_VERSION
+            ));
+        }
+        parse(path, &text)
+    }
+
+    /// What differs between this file and `local` for the years in `years`, one line each
+    pub fn conflicts(&self, local: &SaveFile, years: &Years) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        if self.seed.is_some() && local.seed.is_some() && self.seed != local.seed {
+            conflicts.push("The calendar shuffle is different".to_string());
+        }
+        if let (Some(theirs), Some(ours)) = (self.layout, local.layout)
+            && theirs != ours
+        {
+            conflicts.push(format!(
+                "Layout is {} here and {} in the file",
+                ours.name(),
+                theirs.name()
+            ));
+        }
+        for (year, theirs) in &self.years {
+            // years that aren't here aren't imported either
+            let (Some(ours), Some(calendar)) = (local.years.get(year), years.get(*year)) else {
+                continue;
+            };
+            for day in calendar.days() {
+                let stars = |save: &YearSave| {
+                    save.calendar
+                        .as_ref()
+                        .map_or(0, |calendar| calendar.stars(day))
+                };
+                if stars(ours) != stars(theirs) {
+                    conflicts.push(format!(
+                        "{} day {}: {} stars here, {} in the file",
+                        year,
+                        day,
+                        stars(ours),
+                        stars(theirs)
+                    ));
+                }
+                for puzzle in [Puzzle::Part1, Puzzle::Part2] {
+                    let attempts = |save: &YearSave| {
+                        save.history
+                            .as_ref()
+                            .map_or(0, |history| history.attempts(day, puzzle).len())
+                    };
+                    if attempts(ours) != attempts(theirs) {
+                        conflicts.push(format!(
+                            "{} day {} {}: {} attempts here, {} in the file",
+                            year,
+                            day,
+                            puzzle,
+                            attempts(ours),
+                            attempts(theirs)
+                        ));
+                    }
+                    let manual = |save: &YearSave| {
+                        save.manual_answers
+                            .as_ref()
+                            .and_then(|answers| answers.get(day, puzzle).cloned())
+                    };
+                    if let (Some(ours), Some(theirs)) = (manual(ours), manual(theirs))
+                        && ours != theirs
+                    {
+                        conflicts.push(format!(
+                            "{} day {} {}: manual answer is {} here, {} in the file",
+                            year, day, puzzle, ours, theirs
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// What to save once this file is imported on top of `local`
+    pub fn import(self, mut local: SaveFile, mode: ImportMode) -> SaveFile {
+        match mode {
+            ImportMode::Overwrite => self,
+            ImportMode::Merge => {
+                local.seed = local.seed.or(self.seed);
+                local.layout = local.layout.or(self.layout);
+                local.year = local.year.or(self.year);
+                for (year, theirs) in self.years {
+                    local.years.entry(year).or_default().merge(theirs);
+                }
+                local
+            }
+        }
+    }
+}
+
+/// Just enough of a [`SaveFile`] to tell if it can be read
+#[derive(Deserialize)]
+struct FileVersion {
+    version: u32,
+}
+
+fn is_ron(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "ron")
+}
+
+fn parse<T: DeserializeOwned>(path: &Path, text: &str) -> Result<T, String> {
+    let parsed = if is_ron(path) {
+        ron::from_str(text).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Where the calendar's Export and Import buttons start out writing and reading
+pub fn default_path() -> PathBuf {
+    directories::ProjectDirs::from("", "Phox", "AoB")
+        .map(|dirs| dirs.data_dir().join("progress.json"))
+        .unwrap_or_else(|| PathBuf::from("progress.json"))
+}
+
+/// Trigger to write every year's progress and the settings to a file
+#[derive(Event, Debug, Clone)]
+pub struct ExportProgress(pub PathBuf);
+
+/// Trigger to import a file written by [`ExportProgress`]
+#[derive(Event, Debug, Clone)]
+pub struct ImportProgress {
+    pub path: PathBuf,
+    pub mode: ImportMode,
+}
+
+pub(crate) fn export_progress(
+    export: On<ExportProgress>,
+    pkv: Res<SaveStore>,
+    years: Res<Years>,
+    mut commands: Commands,
+) {
+    match SaveFile::read(&pkv, &years).save(&export.0) {
+        Ok(()) => {
+            let message = format!("Exported progress to {}", export.0.display());
+            info!("{}", message);
+            commands.trigger(Toast::info(message));
+        }
+        Err(e) => {
+            error!("{}", e);
+            commands.trigger(Toast::error(e));
+        }
+    }
+}
+
+pub(crate) fn import_progress(import: On<ImportProgress>, mut commands: Commands) {
+    let import = import.clone();
+    commands.queue(move |world: &mut World| {
+        let result = SaveFile::load(&import.path).and_then(|file| apply(world, file, import.mode));
+        let toast = match result {
+            Ok(()) => Toast::info(format!("Imported progress from {}", import.path.display())),
+            Err(e) => {
+                error!("{}", e);
+                Toast::error(e)
+            }
+        };
+        world.trigger(toast);
+    });
+}
+
+/// Imports `file` then reloads everything that was read from the save
+pub(crate) fn apply(world: &mut World, file: SaveFile, mode: ImportMode) -> Result<(), String> {
+    world.resource_scope(|world, mut pkv: Mut<SaveStore>| {
+        let years = world.resource::<Years>();
+        let local = SaveFile::read(&pkv, years);
+        file.import(local, mode).write(&mut pkv, years)
+    })?;
+    crate::profile::reload_saves(world);
+    Ok(())
+}
+
+/// Export and Import buttons on the calendar, importing shows what would change first
+pub struct TransferPlugin;
+
+impl Plugin for TransferPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TransferPath(default_path()));
+        app.add_systems(OnEnter(Day(0)), spawn_transfer_buttons);
+        app.add_systems(
+            Update,
+            (type_path, update_path_text)
+                .chain()
+                .run_if(resource_exists::<PathPrompt>),
+        );
+    }
+}
+
+/// The file last exported to or imported from, the path prompt starts with it
+#[derive(Resource)]
+struct TransferPath(PathBuf);
+
+/// The path being typed, removed when the prompt closes
+#[derive(Resource)]
+struct PathPrompt {
+    import: bool,
+    text: String,
+}
+
+/// The file being imported, removed when the preview closes
+#[derive(Resource)]
+struct PendingImport(PathBuf);
+
+#[derive(Component)]
+struct ImportPreview;
+
+#[derive(Component)]
+struct PathPanel;
+
+#[derive(Component)]
+struct PathText;
+
+#[derive(Component, Clone, Copy)]
+enum TransferAction {
+    Export,
+    Import,
+    /// Exports to or previews the import of the typed path
+    UsePath,
+    PastePath,
+    OpenFolder,
+    ClosePath,
+    Merge,
+    Overwrite,
+    Cancel,
+}
+
+fn spawn_transfer_buttons(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                column_gap: Val::Px(6.0),
+                ..Default::default()
+            },
+            DespawnOnExit(Day(0)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|p| {
+            for (label, action) in [
+                ("Export", TransferAction::Export),
+                ("Import", TransferAction::Import),
+            ] {
+                p.spawn(button(
+                    ButtonProps::default(),
+                    action,
+                    Spawn(Text::new(label)),
+                ))
+                .observe(on_action);
+            }
+        });
+}
+
+fn on_action(
+    event: On<bevy::ui_widgets::Activate>,
+    actions: Query<&TransferAction>,
+    mut commands: Commands,
+) {
+    let Ok(action) = actions.get(event.entity) else {
+        return;
+    };
+    match action {
+        TransferAction::Export => commands.run_system_cached_with(spawn_path_prompt, false),
+        TransferAction::Import => commands.run_system_cached_with(spawn_path_prompt, true),
+        TransferAction::UsePath => commands.queue(use_path),
+        TransferAction::PastePath => commands.queue(paste_path),
+        TransferAction::OpenFolder => commands.queue(open_folder),
+        TransferAction::ClosePath => commands.queue(close_path_prompt),
+        TransferAction::Merge | TransferAction::Overwrite | TransferAction::Cancel => {
+            let mode = match action {
+                TransferAction::Merge => Some(ImportMode::Merge),
+                TransferAction::Overwrite => Some(ImportMode::Overwrite),
+                _ => None,
+            };
+            commands.queue(move |world: &mut World| close_preview(world, mode));
+        }
+    }
+}
+
+fn spawn_path_prompt(
+    import: In<bool>,
+    path: Res<TransferPath>,
+    old: Query<Entity, With<PathPanel>>,
+    mut commands: Commands,
+) {
+    for panel in &old {
+        commands.entity(panel).despawn();
+    }
+    let (title, confirm) = if *import {
+        ("Import progress from", "Import")
+    } else {
+        ("Export progress to", "Export")
+    };
+    commands.insert_resource(PathPrompt {
+        import: *import,
+        text: path.0.display().to_string(),
+    });
+    let panel = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(60.),
+                left: Val::Percent(20.),
+                top: Val::Percent(10.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.),
+                padding: UiRect::all(Val::Px(20.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(15.)),
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(5),
+            DespawnOnExit(Day(0)),
+            PathPanel,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(format!(
+                    "{}, type or paste a path; files ending in .ron are RON, anything else is JSON",
+                    title
+                )),
+                ThemedText,
+            ));
+            p.spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..Default::default()
+                },
+                BorderRadius::all(Val::Px(8.)),
+                BackgroundColor(Color::BLACK.with_alpha(0.4)),
+                children![(Text::default(), ThemedText, PathText)],
+            ));
+            p.spawn(Node {
+                column_gap: Val::Px(8.),
+                ..Default::default()
+            })
+            .with_children(|p| {
+                for (label, action, variant) in [
+                    (confirm, TransferAction::UsePath, ButtonVariant::Primary),
+                    ("Paste", TransferAction::PastePath, ButtonVariant::Normal),
+                    (
+                        "Open folder",
+                        TransferAction::OpenFolder,
+                        ButtonVariant::Normal,
+                    ),
+                    ("Cancel", TransferAction::ClosePath, ButtonVariant::Normal),
+                ] {
+                    p.spawn(button(
+                        ButtonProps {
+                            variant,
+                            ..Default::default()
+                        },
+                        action,
+                        Spawn(Text::new(label)),
+                    ))
+                    .observe(on_action);
+                }
+            });
+        })
+        .id();
+    // keeps Enter and Space from activating the calendar tile behind the prompt
+    commands.insert_resource(InputFocus(Some(panel)));
+}
+
+fn type_path(
+    mut keys: MessageReader<KeyboardInput>,
+    held: Res<ButtonInput<KeyCode>>,
+    mut prompt: ResMut<PathPrompt>,
+    mut commands: Commands,
+) {
+    let ctrl = held.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Character(c) if ctrl && c.eq_ignore_ascii_case("v") => commands.queue(paste_path),
+            Key::Character(c) if !ctrl => prompt.text.push_str(c),
+            Key::Space => prompt.text.push(' '),
+            Key::Backspace => {
+                prompt.text.pop();
+            }
+            Key::Enter => commands.queue(use_path),
+            Key::Escape => commands.queue(close_path_prompt),
+            _ => {}
+        }
+    }
+}
+
+fn update_path_text(prompt: Res<PathPrompt>, mut texts: Query<&mut Text, With<PathText>>) {
+    if !prompt.is_changed() {
+        return;
+    }
+    for mut text in &mut texts {
+        text.0.clone_from(&prompt.text);
+    }
+}
+
+fn paste_path(world: &mut World) {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        Ok(text) => {
+            if let Some(mut prompt) = world.get_resource_mut::<PathPrompt>() {
+                // a path copied from a file manager often ends in a new line
+                prompt.text = text.trim().to_string();
+            }
+        }
+        Err(e) => world.trigger(Toast::error(format!("Couldn't read the clipboard: {}", e))),
+    }
+}
+
+/// Shows the folder the typed path is in with the system's file manager
+fn open_folder(world: &mut World) {
+    let Some(prompt) = world.get_resource::<PathPrompt>() else {
+        return;
+    };
+    let path = PathBuf::from(prompt.text.trim());
+    let folder = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let opened = std::fs::create_dir_all(&folder)
+        .and_then(|_| std::process::Command::new(program).arg(&folder).spawn());
+    if let Err(e) = opened {
+        let message = format!("Couldn't open {}: {}", folder.display(), e);
+        error!("{}", message);
+        world.trigger(Toast::error(message));
+    }
+}
+
+/// Exports to the typed path, or previews importing it
+fn use_path(world: &mut World) {
+    let Some(prompt) = world.get_resource::<PathPrompt>() else {
+        return;
+    };
+    let text = prompt.text.trim();
+    if text.is_empty() {
+        world.trigger(Toast::error("Type the path of the file first"));
+        return;
+    }
+    let (import, path) = (prompt.import, PathBuf::from(text));
+    world.insert_resource(TransferPath(path.clone()));
+    close_path_prompt(world);
+    if import {
+        _ = world.run_system_cached_with(spawn_import_preview, path);
+    } else {
+        world.trigger(ExportProgress(path));
+    }
+}
+
+fn close_path_prompt(world: &mut World) {
+    world.remove_resource::<PathPrompt>();
+    world.insert_resource(InputFocus(None));
+    let panels = world
+        .query_filtered::<Entity, With<PathPanel>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for panel in panels {
+        world.despawn(panel);
+    }
+}
+
+fn spawn_import_preview(
+    In(path): In<PathBuf>,
+    pkv: Res<SaveStore>,
+    years: Res<Years>,
+    old: Query<Entity, With<ImportPreview>>,
+    mut commands: Commands,
+) {
+    let file = match SaveFile::load(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            error!("{}", e);
+            commands.trigger(Toast::error(e));
+            return;
+        }
+    };
+    for preview in &old {
+        commands.entity(preview).despawn();
+    }
+    let conflicts = file.conflicts(&SaveFile::read(&pkv, &years), &years);
+    let mut lines = vec![format!(
+        "Import {}, exported {}",
+        path.display(),
+        file.exported
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+    )];
+    if conflicts.is_empty() {
+        lines.push("Nothing here conflicts with the file".to_string());
+    } else {
+        lines.push(format!("{} conflicts:", conflicts.len()));
+        lines.extend(conflicts.iter().take(PREVIEW_CONFLICTS).cloned());
+        if conflicts.len() > PREVIEW_CONFLICTS {
+            lines.push(format!(
+                "... and {} more",
+                conflicts.len() - PREVIEW_CONFLICTS
+            ));
+        }
+    }
+    lines.push(
+        "Merge keeps the stars and attempts of both, Overwrite replaces everything here"
+            .to_string(),
+    );
+    commands.insert_resource(PendingImport(path));
+    let preview = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(60.),
+                left: Val::Percent(20.),
+                top: Val::Percent(10.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.),
+                padding: UiRect::all(Val::Px(20.)),
+                ..Default::default()
+            },
+            BorderRadius::all(Val::Px(15.)),
+            ThemeBackgroundColor(tokens::WINDOW_BG),
+            ThemeFontColor(tokens::TEXT_MAIN),
+            GlobalZIndex(5),
+            DespawnOnExit(Day(0)),
+            ImportPreview,
+        ))
+        .with_children(|p| {
+            for line in lines {
+                p.spawn((Text::new(line), ThemedText));
+            }
+            p.spawn(Node {
+                column_gap: Val::Px(8.),
+                ..Default::default()
+            })
+            .with_children(|p| {
+                for (label, action, variant) in [
+                    ("Merge", TransferAction::Merge, ButtonVariant::Primary),
+                    (
+                        "Overwrite",
+                        TransferAction::Overwrite,
+                        ButtonVariant::Normal,
+                    ),
+                    ("Cancel", TransferAction::Cancel, ButtonVariant::Normal),
+                ] {
+                    p.spawn(button(
+                        ButtonProps {
+                            variant,
+                            ..Default::default()
+                        },
+                        action,
+                        Spawn(Text::new(label)),
+                    ))
+                    .observe(on_action);
+                }
+            });
+        })
+        .id();
+    // keeps Enter and Space from activating the calendar tile behind the preview
+    commands.insert_resource(InputFocus(Some(preview)));
+}
+
+/// Closes the preview, importing the file with `mode` if there is one
+fn close_preview(world: &mut World, mode: Option<ImportMode>) {
+    let pending = world.remove_resource::<PendingImport>();
+    world.insert_resource(InputFocus(None));
+    let previews = world
+        .query_filtered::<Entity, With<ImportPreview>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for preview in previews {
+        world.despawn(preview);
+    }
+    if let (Some(PendingImport(path)), Some(mode)) = (pending, mode) {
+        world.trigger(ImportProgress { path, mode });
+    }
+}
+
+#[test]
+fn import_merges_or_overwrites() {
+    let file = |seed, days: &str| SaveFile {
+        version: SAVE_VERSION,
+        exported: Utc::now(),
+        seed: Some(seed),
+        layout: None,
+        year: None,
+        years: BTreeMap::from([(
+            2025,
+            YearSave {
+                calendar: Some(serde_json::from_str(days).unwrap()),
+                ..Default::default()
+            },
+        )]),
+    };
+    let ours = || {
+        file(
+            1,
+            r#"{"days": [{"puzzle1_completed": true, "puzzle2_completed": false},
+                         {"puzzle1_completed": false, "puzzle2_completed": false}]}"#,
+        )
+    };
+    let theirs = file(
+        2,
+        r#"{"days": [{"puzzle1_completed": false, "puzzle2_completed": false},
+                     {"puzzle1_completed": true, "puzzle2_completed": true}]}"#,
+    );
+    // the shuffle and both days
+    let years = Years(BTreeMap::from([(2025, Year::aoc(2025))]));
+    assert_eq!(theirs.conflicts(&ours(), &years).len(), 3);
+    // only the shuffle when 2025 isn't a year here
+    assert_eq!(theirs.conflicts(&ours(), &Years::default()).len(), 1);
+    let path = std::env::temp_dir().join("aob_import_merges_or_overwrites.ron");
+    theirs.save(&path).unwrap();
+    let merged = SaveFile::load(&path)
+        .unwrap()
+        .import(ours(), ImportMode::Merge);
+    assert_eq!(merged.seed, Some(1));
+    let calendar = merged.years[&2025].calendar.as_ref().unwrap();
+    assert_eq!((calendar.stars(1), calendar.stars(2)), (1, 2));
+    let overwritten = SaveFile::load(&path)
+        .unwrap()
+        .import(ours(), ImportMode::Overwrite);
+    assert_eq!(overwritten.seed, Some(2));
+    assert_eq!(
+        overwritten.years[&2025].calendar.as_ref().unwrap().stars(1),
+        0
+    );
+    _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+
+/// The newest event, the [`Year`](crate::Year) opened until another is picked
+pub const EVENT_YEAR: i32 = 2025;
+
+/// Puzzles unlock at midnight US Eastern, which AoC keeps at UTC-5
+const UNLOCK_OFFSET: i32 = -5 * 60 * 60;
+
+/// Which days of the calendar can be opened, days without a solver are always locked
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub enum UnlockPolicy {
+    /// Like AoC, a new day each midnight US Eastern in December of the calendar's year, and every day after that
+    #[default]
+    Official,
+    All,
+    /// Day 1, then each day once the one before has its first star
+    Sequential,
+    /// Only these days
+    Manual(Vec<u8>),
+}
+
+impl UnlockPolicy {
+    /// Whether `day` of `year` can be opened, `first_star` says whether a day has its first star
+    pub fn is_unlocked(
+        &self,
+        year: i32,
+        day: u8,
+        now: DateTime<Utc>,
+        first_star: impl Fn(u8) -> bool,
+    ) -> bool {
+        match self {
+            UnlockPolicy::Official => official_unlocked(year, day, now),
+            UnlockPolicy::All => true,
+            UnlockPolicy::Sequential => day <= 1 || first_star(day - 1),
+            UnlockPolicy::Manual(days) => days.contains(&day),
+        }
+    }
+}
+
+fn official_unlocked(year: i32, day: u8, now: DateTime<Utc>) -> bool {
+    let Some(offset) = FixedOffset::east_opt(UNLOCK_OFFSET) else {
+        return false;
+    };
+    let now = now.with_timezone(&offset);
+    match now.year().cmp(&year) {
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => now.month() == 12 && day as u32 <= now.day(),
+        std::cmp::Ordering::Greater => true,
+    }
+}
+
+#[test]
+fn official_unlock_times() {
+    let at = |time: &str| time.parse::<DateTime<Utc>>().unwrap();
+    let policy = UnlockPolicy::Official;
+    let no_stars = |_| false;
+    assert!(!policy.is_unlocked(EVENT_YEAR, 1, at("2025-11-30T12:00:00Z"), no_stars));
+    assert!(!policy.is_unlocked(EVENT_YEAR, 5, at("2025-12-05T04:59:59Z"), no_stars));
+    assert!(policy.is_unlocked(EVENT_YEAR, 5, at("2025-12-05T05:00:00Z"), no_stars));
+    assert!(!policy.is_unlocked(EVENT_YEAR, 6, at("2025-12-05T05:00:00Z"), no_stars));
+    assert!(policy.is_unlocked(EVENT_YEAR, 25, at("2026-01-02T00:00:00Z"), no_stars));
+    assert!(policy.is_unlocked(2024, 25, at("2025-12-01T00:00:00Z"), no_stars));
+    assert!(UnlockPolicy::Sequential.is_unlocked(
+        EVENT_YEAR,
+        3,
+        at("2020-01-01T00:00:00Z"),
+        |day| day == 2
+    ));
+}
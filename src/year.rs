@@ -0,0 +1,191 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use bevy::prelude::*;
+
+use crate::{
+    Anwsers, Cooldowns, Day, EVENT_YEAR, InputSelection, PKVKeys, ProgressStore, SubmissionHistory,
+    Timings, Toast, calendar::CalendarState, input::InputRoot,
+};
+
+/// An Advent of Code event, each has its own calendar, saves and input folder
+#[derive(Resource, Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Year {
+    pub year: i32,
+    pub days: u8,
+}
+
+impl Year {
+    pub const fn new(year: i32, days: u8) -> Year {
+        Year { year, days }
+    }
+
+    /// The year with as many days as AoC had, 25 until 2025 which has 12
+    pub const fn aoc(year: i32) -> Year {
+        Year::new(year, if year < 2025 { 25 } else { 12 })
+    }
+
+    pub fn days(&self) -> RangeInclusive<u8> {
+        1..=self.days
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.days().contains(&day)
+    }
+
+    /// The key this year's `key` save is kept under, e.g. `History2025`
+    pub fn key(&self, key: PKVKeys) -> String {
+        format!("{}{}", key.as_ref(), self.year)
+    }
+}
+
+/// The year that was open last, or [`Years::default_year`]
+impl FromWorld for Year {
+    fn from_world(world: &mut World) -> Self {
+        let years = world.get_resource_or_init::<Years>();
+        let default = years.default_year();
+        let saved = world
+            .resource::<crate::SaveStore>()
+            .get::<i32>(PKVKeys::Year)
+            .ok();
+        saved
+            .and_then(|year| world.resource::<Years>().get(year))
+            .unwrap_or(default)
+    }
+}
+
+/// Every year that can be picked, see [`AddYear::add_year`]
+#[derive(Resource, Debug, Default)]
+pub struct Years(pub BTreeMap<i32, Year>);
+
+impl Years {
+    pub fn get(&self, year: i32) -> Option<Year> {
+        self.0.get(&year).copied()
+    }
+
+    /// [`EVENT_YEAR`] if it has been added, otherwise the newest year
+    pub fn default_year(&self) -> Year {
+        self.get(EVENT_YEAR)
+            .or_else(|| self.0.values().last().copied())
+            .unwrap_or(Year::aoc(EVENT_YEAR))
+    }
+}
+
+pub trait AddYear {
+    /// Adds a year to the picker, years a day is added for are added with [`Year::aoc`] if they aren't yet
+    fn add_year(&mut self, year: Year) -> &mut Self;
+}
+
+impl AddYear for App {
+    fn add_year(&mut self, year: Year) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<Years>()
+            .0
+            .insert(year.year, year);
+        self
+    }
+}
+
+/// Run condition for systems that belong to one year's days
+pub fn in_year(year: i32) -> impl Fn(Res<Year>) -> bool + Clone {
+    move |current: Res<Year>| current.year == year
+}
+
+/// Trigger to switch the calendar to another year, [`Year`] is triggered once it has
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SelectYear(pub i32);
+
+pub(crate) fn select_year(
+    select: On<SelectYear>,
+    years: Res<Years>,
+    day: Res<State<Day>>,
+    mut commands: Commands,
+) {
+    let Some(year) = years.get(select.0) else {
+        let message = format!("There is no {} calendar", select.0);
+        error!("{}", message);
+        commands.trigger(Toast::error(message));
+        return;
+    };
+    if day.0 != 0 {
+        commands.trigger(Toast::error("Go back to the calendar to change year"));
+        return;
+    }
+    commands.queue(move |world: &mut World| {
+        open_year(world, year);
+        world.trigger(year);
+    });
+}
+
+/// Makes `year` the open year, loading its saves and inputs
+pub(crate) fn open_year(world: &mut World, year: Year) {
+    world.insert_resource(year);
+    let dir = world.resource::<InputRoot>().year(year.year);
+    if let Err(e) = dir.create_missing(year.days) {
+        error!("Failed to create inputs in {}: {}", dir.0.display(), e);
+    }
+    world.insert_resource(dir);
+    world.insert_resource(InputSelection::default());
+    let calendar = CalendarState::from_world(world);
+    world.insert_resource(calendar);
+    let history = SubmissionHistory::from_world(world);
+    world.insert_resource(history);
+    let cooldowns = Cooldowns::from_world(world);
+    world.insert_resource(cooldowns);
+    let timings = Timings::from_world(world);
+    world.insert_resource(timings);
+    let mut answers = Anwsers::from_world(world);
+    if let Some(old) = world.remove_resource::<Anwsers>() {
+        answers.keep_registered(old);
+    }
+    world.insert_resource(answers);
+    if let Err(e) = world
+        .resource_mut::<crate::SaveStore>()
+        .set(PKVKeys::Year, &year.year)
+    {
+        error!("Failed to save the open year: {}", e);
+    }
+}
+
+/// Saves from before there were years are all [`EVENT_YEAR`]'s
+pub(crate) fn migrate_save(store: &mut dyn ProgressStore) {
+    let year = Year::aoc(EVENT_YEAR);
+    move_save(
+        store,
+        PKVKeys::CalendarState25,
+        year.key(PKVKeys::CalendarState),
+    );
+    move_save(store, PKVKeys::History, year.key(PKVKeys::History));
+    move_save(
+        store,
+        PKVKeys::ManualAnswers,
+        year.key(PKVKeys::ManualAnswers),
+    );
+    move_save(store, PKVKeys::Cooldowns, year.key(PKVKeys::Cooldowns));
+}
+
+/// Moves a save to a new key as it is, unless there is already one there
+pub(crate) fn move_save(store: &mut dyn ProgressStore, from: impl AsRef<str>, to: String) {
+    let from = from.as_ref();
+    let Ok(save) = store.get(from) else {
+        return;
+    };
+    if store.get(&to).is_ok() {
+        return;
+    }
+    match store.set(&to, save) {
+        Ok(()) => {
+            info!("Moved the {} save to {}", from, to);
+            _ = store.remove(from);
+        }
+        Err(e) => error!("Failed to move the {} save: {}", from, e),
+    }
+}
+
+#[test]
+fn year_keys_and_days() {
+    let year = Year::aoc(2025);
+    assert_eq!(year.days, 12);
+    assert_eq!(Year::aoc(2024).days, 25);
+    assert_eq!(year.key(PKVKeys::History), "History2025");
+    assert!(year.contains(12) && !year.contains(13) && !year.contains(0));
+}